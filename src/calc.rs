@@ -0,0 +1,1030 @@
+use crate::node::{Function, Node};
+use crate::number::non_finite_keyword;
+use crate::parser;
+use crate::quantity::{ArithmeticError, Quantity};
+use crate::unit::{canonical_unit, unit, UnitKind};
+use std::cmp::Ordering;
+use std::f64::consts;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// A calculation tree, as described by
+/// https://www.w3.org/TR/css-values-4/#calc-internal
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+  /// A number, percentage or dimension
+  Value(Quantity),
+  Sum(Vec<Expr>),
+  Negate(Box<Expr>),
+  Product(Vec<Expr>),
+  Invert(Box<Expr>),
+  /// A math function with its arguments
+  Function(MathFunction, Vec<Expr>),
+  /// A value that can't be computed ahead of time, like `var(--x)`, kept as written
+  Opaque(String),
+}
+
+/// The math functions that can appear in a calculation besides `calc()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathFunction {
+  Min,
+  Max,
+  Clamp,
+  Round(RoundingStrategy),
+  Mod,
+  Rem,
+  Abs,
+  Sign,
+  Sin,
+  Cos,
+  Tan,
+  Asin,
+  Acos,
+  Atan,
+  Atan2,
+  Pow,
+  Sqrt,
+  Hypot,
+  Log,
+  Exp,
+}
+
+/// The first, optional, argument of `round()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingStrategy {
+  #[default]
+  Nearest,
+  Up,
+  Down,
+  ToZero,
+}
+
+impl MathFunction {
+  pub fn from_name(name: &str) -> Option<MathFunction> {
+    let name = name.to_ascii_lowercase();
+    Some(match name.as_str() {
+      "min" => MathFunction::Min,
+      "max" => MathFunction::Max,
+      "clamp" => MathFunction::Clamp,
+      "round" => MathFunction::Round(RoundingStrategy::Nearest),
+      "mod" => MathFunction::Mod,
+      "rem" => MathFunction::Rem,
+      "abs" => MathFunction::Abs,
+      "sign" => MathFunction::Sign,
+      "sin" => MathFunction::Sin,
+      "cos" => MathFunction::Cos,
+      "tan" => MathFunction::Tan,
+      "asin" => MathFunction::Asin,
+      "acos" => MathFunction::Acos,
+      "atan" => MathFunction::Atan,
+      "atan2" => MathFunction::Atan2,
+      "pow" => MathFunction::Pow,
+      "sqrt" => MathFunction::Sqrt,
+      "hypot" => MathFunction::Hypot,
+      "log" => MathFunction::Log,
+      "exp" => MathFunction::Exp,
+      _ => return None,
+    })
+  }
+
+  pub fn name(self) -> &'static str {
+    match self {
+      MathFunction::Min => "min",
+      MathFunction::Max => "max",
+      MathFunction::Clamp => "clamp",
+      MathFunction::Round(_) => "round",
+      MathFunction::Mod => "mod",
+      MathFunction::Rem => "rem",
+      MathFunction::Abs => "abs",
+      MathFunction::Sign => "sign",
+      MathFunction::Sin => "sin",
+      MathFunction::Cos => "cos",
+      MathFunction::Tan => "tan",
+      MathFunction::Asin => "asin",
+      MathFunction::Acos => "acos",
+      MathFunction::Atan => "atan",
+      MathFunction::Atan2 => "atan2",
+      MathFunction::Pow => "pow",
+      MathFunction::Sqrt => "sqrt",
+      MathFunction::Hypot => "hypot",
+      MathFunction::Log => "log",
+      MathFunction::Exp => "exp",
+    }
+  }
+
+  /// The accepted number of arguments, not counting the rounding strategy
+  fn arity(self) -> RangeInclusive<usize> {
+    match self {
+      MathFunction::Min | MathFunction::Max | MathFunction::Hypot => 1..=usize::MAX,
+      MathFunction::Clamp => 3..=3,
+      MathFunction::Round(_) | MathFunction::Log => 1..=2,
+      MathFunction::Mod | MathFunction::Rem | MathFunction::Atan2 | MathFunction::Pow => 2..=2,
+      _ => 1..=1,
+    }
+  }
+}
+
+impl RoundingStrategy {
+  pub fn from_name(name: &str) -> Option<RoundingStrategy> {
+    let name = name.to_ascii_lowercase();
+    match name.as_str() {
+      "nearest" => Some(RoundingStrategy::Nearest),
+      "up" => Some(RoundingStrategy::Up),
+      "down" => Some(RoundingStrategy::Down),
+      "to-zero" => Some(RoundingStrategy::ToZero),
+      _ => None,
+    }
+  }
+
+  pub fn name(self) -> &'static str {
+    match self {
+      RoundingStrategy::Nearest => "nearest",
+      RoundingStrategy::Up => "up",
+      RoundingStrategy::Down => "down",
+      RoundingStrategy::ToZero => "to-zero",
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SimplifyOptions {
+  /// The root font size in `px`, letting `rem` values be compared with `px` ones
+  pub root_font_size: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalcError {
+  /// A node that has no place in a math expression
+  UnexpectedNode(String),
+  UnexpectedEnd,
+  /// `+` and `-` have to be surrounded by whitespace
+  MissingWhitespace(String),
+  /// A math function was given the wrong number of arguments
+  ArgumentCount(MathFunction),
+  /// A math function was given an argument of a type it doesn't accept, like `sin(1px)`
+  ArgumentType(MathFunction),
+  Arithmetic(ArithmeticError),
+}
+
+impl fmt::Display for CalcError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      CalcError::UnexpectedNode(node) => write!(f, "unexpected `{}` in math expression", node),
+      CalcError::UnexpectedEnd => write!(f, "unexpected end of math expression"),
+      CalcError::MissingWhitespace(operator) => {
+        write!(f, "`{}` has to be surrounded by whitespace", operator)
+      }
+      CalcError::ArgumentCount(function) => {
+        write!(f, "wrong number of arguments to `{}()`", function.name())
+      }
+      CalcError::ArgumentType(function) => {
+        write!(f, "invalid argument type for `{}()`", function.name())
+      }
+      CalcError::Arithmetic(error) => error.fmt(f),
+    }
+  }
+}
+
+impl std::error::Error for CalcError {}
+
+impl From<ArithmeticError> for CalcError {
+  fn from(error: ArithmeticError) -> Self {
+    CalcError::Arithmetic(error)
+  }
+}
+
+/// Whether a function name is `calc` or one of its prefixed forms
+pub fn is_calc(name: &str) -> bool {
+  ["calc", "-webkit-calc", "-moz-calc"]
+    .iter()
+    .any(|calc| name.eq_ignore_ascii_case(calc))
+}
+
+/// Parse the arguments of a `calc()` function
+pub fn parse(nodes: &[Node]) -> Result<Expr, CalcError> {
+  let mut parser = Parser {
+    // comments may appear anywhere and mean nothing
+    nodes: nodes
+      .iter()
+      .filter(|node| !matches!(node, Node::Comment(_)))
+      .collect(),
+    index: 0,
+  };
+  let expr = parser.parse_sum()?;
+  parser.skip_space();
+  match parser.peek() {
+    Some(node) => Err(CalcError::UnexpectedNode(node.to_string())),
+    None => Ok(expr),
+  }
+}
+
+pub fn parse_function(function: &Function) -> Result<Expr, CalcError> {
+  parse(&function.nodes)
+}
+
+/// Parse a math function node, `calc()` as well as `min()`, `max()` and friends
+pub fn parse_math_function(function: &Function) -> Result<Expr, CalcError> {
+  match MathFunction::from_name(&function.value) {
+    Some(math_function) => parse_arguments(math_function, &function.nodes),
+    None => parse_function(function),
+  }
+}
+
+fn parse_arguments(mut function: MathFunction, nodes: &[Node]) -> Result<Expr, CalcError> {
+  let mut args = nodes
+    .split(|node| matches!(node, Node::Div(div) if div.value == ","))
+    .peekable();
+  if let MathFunction::Round(strategy) = &mut function {
+    let first = args.peek().and_then(|arg| {
+      let mut words = arg
+        .iter()
+        .filter(|node| !matches!(node, Node::Space(_) | Node::Comment(_)));
+      match (words.next(), words.next()) {
+        (Some(Node::Word(word)), None) => RoundingStrategy::from_name(&word.value),
+        _ => None,
+      }
+    });
+    if let Some(first) = first {
+      *strategy = first;
+      args.next();
+    }
+  }
+  let args = args.map(parse).collect::<Result<Vec<_>, _>>()?;
+  if function.arity().contains(&args.len()) {
+    Ok(Expr::Function(function, args))
+  } else {
+    Err(CalcError::ArgumentCount(function))
+  }
+}
+
+/// Whether a function node is a math function this module can simplify
+pub fn is_math_function(name: &str) -> bool {
+  is_calc(name) || MathFunction::from_name(name).is_some()
+}
+
+/// Simplify every math function in the tree. Calculations that fail to parse or simplify are
+/// left as they are.
+pub fn transform(nodes: &mut [Node]) {
+  transform_with_options(nodes, &Default::default())
+}
+
+pub fn transform_with_options(nodes: &mut [Node], options: &SimplifyOptions) {
+  for node in nodes.iter_mut() {
+    if let Node::Function(function) = node {
+      if is_math_function(&function.value) {
+        if let Some(replacement) = simplify_function(function, options) {
+          *node = replacement;
+          continue;
+        }
+      }
+      transform_with_options(&mut function.nodes, options);
+    }
+  }
+}
+
+fn simplify_function(function: &Function, options: &SimplifyOptions) -> Option<Node<'static>> {
+  let parsed = parse_math_function(function).ok()?;
+  let expr = parsed.clone().simplify_with_options(options).ok()?;
+  // a calculation that didn't simplify is left as written
+  let value = serialize(&expr);
+  if !matches!(expr, Expr::Value(_)) && value == serialize(&parsed) {
+    return None;
+  }
+  let mut nodes = parser::parse(&value);
+  if nodes.len() != 1 {
+    return None;
+  }
+  let node = nodes.pop()?.into_owned();
+  Some(match node {
+    Node::Word(mut word) => {
+      word.span = function.span;
+      Node::Word(word)
+    }
+    Node::Function(mut replacement) => {
+      replacement.span = function.span;
+      Node::Function(replacement)
+    }
+    node => node,
+  })
+}
+
+/// A number or dimension, possibly computed by a math function
+pub(crate) fn value(node: &Node) -> Option<Expr> {
+  match node {
+    Node::Word(word) => {
+      let quantity = Quantity::from_dimension(&unit(&word.value)?);
+      (quantity.kind() != UnitKind::Unknown).then_some(Expr::Value(quantity))
+    }
+    Node::Function(function) if is_math_function(&function.value) => {
+      parse_math_function(function).and_then(Expr::simplify).ok()
+    }
+    _ => None,
+  }
+}
+
+/// Serialize a calculation as a value: a lone value or math function as is, anything else
+/// wrapped in `calc()`
+pub fn serialize(expr: &Expr) -> String {
+  match expr {
+    Expr::Value(quantity) => quantity.to_string(),
+    Expr::Function(..) => expr.to_string(),
+    expr => format!("calc({})", expr),
+  }
+}
+
+struct Parser<'n, 'a> {
+  nodes: Vec<&'n Node<'a>>,
+  index: usize,
+}
+
+impl<'n, 'a> Parser<'n, 'a> {
+  fn peek(&self) -> Option<&'n Node<'a>> {
+    self.nodes.get(self.index).copied()
+  }
+
+  fn next(&mut self) -> Option<&'n Node<'a>> {
+    let node = self.peek();
+    self.index += 1;
+    node
+  }
+
+  /// Returns whether there was any whitespace
+  fn skip_space(&mut self) -> bool {
+    let start = self.index;
+    while let Some(Node::Space(_)) = self.peek() {
+      self.index += 1;
+    }
+    self.index > start
+  }
+
+  fn parse_sum(&mut self) -> Result<Expr, CalcError> {
+    self.skip_space();
+    let mut terms = vec![self.parse_product()?];
+    loop {
+      let save = self.index;
+      let spaced = self.skip_space();
+      let operator = match self.peek() {
+        Some(Node::Word(word)) if word.value == "+" || word.value == "-" => &word.value,
+        _ => {
+          self.index = save;
+          break;
+        }
+      };
+      self.index += 1;
+      if !spaced || !self.skip_space() {
+        return Err(CalcError::MissingWhitespace(operator.to_string()));
+      }
+      let term = self.parse_product()?;
+      terms.push(if operator == "-" {
+        Expr::Negate(Box::new(term))
+      } else {
+        term
+      });
+    }
+    Ok(if terms.len() == 1 {
+      terms.pop().unwrap()
+    } else {
+      Expr::Sum(terms)
+    })
+  }
+
+  fn parse_product(&mut self) -> Result<Expr, CalcError> {
+    let mut factors = vec![self.parse_value()?];
+    loop {
+      let save = self.index;
+      self.skip_space();
+      match self.peek() {
+        Some(Node::Word(word)) if word.value == "*" => {
+          self.index += 1;
+          self.skip_space();
+          factors.push(self.parse_value()?);
+        }
+        Some(Node::Div(div)) if div.value == "/" => {
+          self.index += 1;
+          self.skip_space();
+          factors.push(Expr::Invert(Box::new(self.parse_value()?)));
+        }
+        _ => {
+          self.index = save;
+          break;
+        }
+      }
+    }
+    Ok(if factors.len() == 1 {
+      factors.pop().unwrap()
+    } else {
+      Expr::Product(factors)
+    })
+  }
+
+  fn parse_value(&mut self) -> Result<Expr, CalcError> {
+    match self.next() {
+      Some(Node::Word(word)) if constant(&word.value).is_some() => Ok(Expr::Value(
+        Quantity::number(constant(&word.value).unwrap()),
+      )),
+      Some(Node::Word(word)) => match unit(&word.value) {
+        Some(dimension) if is_unit(dimension.unit) => {
+          Ok(Expr::Value(Quantity::from_dimension(&dimension)))
+        }
+        _ => Err(CalcError::UnexpectedNode(word.value.to_string())),
+      },
+      Some(Node::Function(function))
+        if function.value.is_empty() || is_math_function(&function.value) =>
+      {
+        parse_math_function(function)
+      }
+      Some(node @ (Node::Function(_) | Node::Placeholder(_))) => Ok(Expr::Opaque(node.to_string())),
+      Some(node) => Err(CalcError::UnexpectedNode(node.to_string())),
+      None => Err(CalcError::UnexpectedEnd),
+    }
+  }
+}
+
+/// The value of the keywords that stand for numbers in math expressions
+fn constant(word: &str) -> Option<f64> {
+  let word = word.to_ascii_lowercase();
+  match word.as_str() {
+    "e" => Some(consts::E),
+    "pi" => Some(consts::PI),
+    "infinity" => Some(f64::INFINITY),
+    "-infinity" => Some(f64::NEG_INFINITY),
+    "nan" => Some(f64::NAN),
+    _ => None,
+  }
+}
+
+/// Whether the rest of a word after its number is a unit rather than garbage like `px+1px`
+fn is_unit(unit: &str) -> bool {
+  unit == "%"
+    || unit
+      .bytes()
+      .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'\\') || b >= 0x80)
+}
+
+impl Expr {
+  /// Simplify the calculation tree, combining every value that can be combined ahead of time
+  ///
+  /// https://www.w3.org/TR/css-values-4/#calc-simplification
+  pub fn simplify(self) -> Result<Expr, CalcError> {
+    self.simplify_with_options(&Default::default())
+  }
+
+  pub fn simplify_with_options(self, options: &SimplifyOptions) -> Result<Expr, CalcError> {
+    Ok(match self {
+      Expr::Value(_) | Expr::Opaque(_) => self,
+      Expr::Negate(inner) => negate(inner.simplify_with_options(options)?),
+      Expr::Invert(inner) => match inner.simplify_with_options(options)? {
+        Expr::Value(quantity) if quantity.is_number() => {
+          Expr::Value(Quantity::number(1.0 / quantity.value))
+        }
+        Expr::Invert(inner) => *inner,
+        inner => Expr::Invert(Box::new(inner)),
+      },
+      Expr::Sum(terms) => simplify_sum(terms, options)?,
+      Expr::Product(factors) => simplify_product(factors, options)?,
+      Expr::Function(function, args) => {
+        let args = args
+          .into_iter()
+          .map(|arg| arg.simplify_with_options(options))
+          .collect::<Result<Vec<_>, _>>()?;
+        match function {
+          MathFunction::Min | MathFunction::Max | MathFunction::Clamp => {
+            simplify_comparison(function, args, options)
+          }
+          function => simplify_math(function, args, options)?,
+        }
+      }
+    })
+  }
+}
+
+/// Negate an expression, pushing the negation into values where possible
+fn negate(expr: Expr) -> Expr {
+  match expr {
+    Expr::Value(quantity) => Expr::Value(-quantity),
+    Expr::Negate(inner) => *inner,
+    Expr::Sum(terms) => Expr::Sum(terms.into_iter().map(negate).collect()),
+    Expr::Product(mut factors) => match factors
+      .iter()
+      .position(|factor| matches!(factor, Expr::Value(_)))
+    {
+      Some(index) => {
+        let factor = factors.remove(index);
+        factors.insert(index, negate(factor));
+        Expr::Product(factors)
+      }
+      None => Expr::Negate(Box::new(Expr::Product(factors))),
+    },
+    expr => Expr::Negate(Box::new(expr)),
+  }
+}
+
+fn simplify_sum(terms: Vec<Expr>, options: &SimplifyOptions) -> Result<Expr, CalcError> {
+  let mut result = vec![];
+  for term in terms {
+    match term.simplify_with_options(options)? {
+      Expr::Sum(inner) => {
+        for term in inner {
+          add_term(&mut result, term)?;
+        }
+      }
+      term => add_term(&mut result, term)?,
+    }
+  }
+  Ok(if result.len() == 1 {
+    result.pop().unwrap()
+  } else {
+    Expr::Sum(result)
+  })
+}
+
+/// Add a term to a sum, merging it into a value of a compatible unit if there is one
+fn add_term(terms: &mut Vec<Expr>, term: Expr) -> Result<(), CalcError> {
+  if let Expr::Value(quantity) = &term {
+    for existing in terms.iter_mut() {
+      if let Expr::Value(existing) = existing {
+        // different compatible units are summed up in their canonical unit
+        let left = match canonical_unit(&existing.unit) {
+          Some((canonical, _)) if !existing.unit.eq_ignore_ascii_case(&quantity.unit) => {
+            existing.to(canonical).unwrap_or_else(|| existing.clone())
+          }
+          _ => existing.clone(),
+        };
+        match left + quantity.clone() {
+          Ok(sum) => {
+            *existing = sum;
+            return Ok(());
+          }
+          Err(ArithmeticError::Unresolved { .. }) => {}
+          Err(error) => return Err(error.into()),
+        }
+      }
+    }
+  }
+  terms.push(term);
+  Ok(())
+}
+
+fn simplify_product(factors: Vec<Expr>, options: &SimplifyOptions) -> Result<Expr, CalcError> {
+  let mut flat = vec![];
+  for factor in factors {
+    match factor {
+      // keep divisions as such, `100 / 3` is more precise than `100 * (1 / 3)`
+      Expr::Invert(inner) => flat.push(Expr::Invert(Box::new(
+        inner.simplify_with_options(options)?,
+      ))),
+      factor => match factor.simplify_with_options(options)? {
+        Expr::Product(inner) => flat.extend(inner),
+        factor => flat.push(factor),
+      },
+    }
+  }
+
+  let mut number = 1.0;
+  let mut divisor = 1.0;
+  let mut dimensions: Vec<Quantity> = vec![];
+  let mut divisors: Vec<Quantity> = vec![];
+  let mut rest = vec![];
+  for factor in flat {
+    match factor {
+      Expr::Value(quantity) if quantity.is_number() => number *= quantity.value,
+      Expr::Value(quantity) => dimensions.push(quantity),
+      Expr::Invert(inner) => match *inner {
+        Expr::Value(quantity) if quantity.is_number() => divisor *= quantity.value,
+        Expr::Value(quantity) => divisors.push(quantity),
+        inner => rest.push(Expr::Invert(Box::new(inner))),
+      },
+      factor => rest.push(factor),
+    }
+  }
+  // `10px / 2px` cancels out to a number
+  for divisor in divisors {
+    let cancelled = dimensions
+      .iter()
+      .enumerate()
+      .find_map(|(index, dimension)| {
+        (dimension.clone() / divisor.clone())
+          .ok()
+          .map(|quotient| (index, quotient))
+      });
+    match cancelled {
+      Some((index, quotient)) => {
+        dimensions.remove(index);
+        number *= quotient.value;
+      }
+      // what's left, like `1px / 1s`, has no CSS type
+      None => {
+        return Err(CalcError::Arithmetic(ArithmeticError::IncompatibleUnits {
+          left: dimensions
+            .first()
+            .map_or_else(String::new, |dimension| dimension.unit.clone()),
+          right: divisor.unit,
+        }))
+      }
+    }
+  }
+  // a product of dimensions, like `1px * 1px`, has no CSS type
+  if let [left, right, ..] = dimensions.as_slice() {
+    return Err(CalcError::Arithmetic(ArithmeticError::IncompatibleUnits {
+      left: left.unit.clone(),
+      right: right.unit.clone(),
+    }));
+  }
+
+  // a number times a sum of values multiplies each of them
+  if dimensions.is_empty() && rest.len() == 1 {
+    if let Expr::Sum(terms) = &rest[0] {
+      if terms.iter().all(|term| matches!(term, Expr::Value(_))) {
+        let terms = terms
+          .iter()
+          .map(|term| match term {
+            Expr::Value(quantity) => Expr::Value(Quantity::new(
+              quantity.value * number / divisor,
+              quantity.unit.clone(),
+            )),
+            _ => unreachable!(),
+          })
+          .collect();
+        return Ok(Expr::Sum(terms));
+      }
+    }
+  }
+
+  let mut result = vec![];
+  if let [dimension] = dimensions.as_mut_slice() {
+    dimension.value = dimension.value * number / divisor;
+    number = 1.0;
+  } else {
+    number /= divisor;
+  }
+  if number != 1.0 || (dimensions.is_empty() && rest.is_empty()) {
+    result.push(Expr::Value(Quantity::number(number)));
+  }
+  result.extend(dimensions.into_iter().map(Expr::Value));
+  result.extend(rest);
+  Ok(if result.len() == 1 {
+    result.pop().unwrap()
+  } else {
+    Expr::Product(result)
+  })
+}
+
+/// Turn `rem` into `px` when the root font size is known
+fn resolve(quantity: &Quantity, options: &SimplifyOptions) -> Quantity {
+  match options.root_font_size {
+    Some(root_font_size) if quantity.unit.eq_ignore_ascii_case("rem") => {
+      Quantity::new(quantity.value * root_font_size, "px")
+    }
+    _ => quantity.clone(),
+  }
+}
+
+/// Compare two values, `None` if they can't be compared ahead of time
+fn compare(left: &Quantity, right: &Quantity, options: &SimplifyOptions) -> Option<Ordering> {
+  let (left, right) = (resolve(left, options), resolve(right, options));
+  let right = right.to(&left.unit)?;
+  left.value.partial_cmp(&right.value)
+}
+
+/// Bring an argument to the unit of another one it has to have the same type as.
+/// Returns the first argument, possibly resolved, and the value of the second in its unit,
+/// or `None` if they can only be brought together at computed-value time.
+fn align(
+  left: &Quantity,
+  right: &Quantity,
+  options: &SimplifyOptions,
+) -> Result<Option<(Quantity, f64)>, CalcError> {
+  if let Some(right) = right.to(&left.unit) {
+    return Ok(Some((left.clone(), right.value)));
+  }
+  let (left, right) = (resolve(left, options), resolve(right, options));
+  if let Some(right) = right.to(&left.unit) {
+    return Ok(Some((left, right.value)));
+  }
+  match left - right {
+    Err(ArithmeticError::Unresolved { .. }) | Ok(_) => Ok(None),
+    Err(error) => Err(error.into()),
+  }
+}
+
+/// The value of a number argument, `None` for a percentage that only resolves to one later
+fn number_argument(function: MathFunction, quantity: &Quantity) -> Result<Option<f64>, CalcError> {
+  match quantity.kind() {
+    UnitKind::Number => Ok(Some(quantity.value)),
+    UnitKind::Percentage => Ok(None),
+    _ => Err(CalcError::ArgumentType(function)),
+  }
+}
+
+/// The value in radians of an angle or number argument to a trigonometric function
+fn angle_argument(function: MathFunction, quantity: &Quantity) -> Result<Option<f64>, CalcError> {
+  match quantity.kind() {
+    UnitKind::Angle => Ok(quantity.to("rad").map(|radians| radians.value)),
+    _ => number_argument(function, quantity),
+  }
+}
+
+/// Compute the math functions other than the comparison ones, once all of their arguments
+/// are values
+///
+/// https://www.w3.org/TR/css-values-4/#math
+fn simplify_math(
+  function: MathFunction,
+  args: Vec<Expr>,
+  options: &SimplifyOptions,
+) -> Result<Expr, CalcError> {
+  let values = args
+    .iter()
+    .map(|arg| match arg {
+      Expr::Value(quantity) => Some(quantity),
+      _ => None,
+    })
+    .collect::<Option<Vec<_>>>();
+  let result = match values {
+    Some(values) => evaluate(function, &values, options)?,
+    None => None,
+  };
+  Ok(match result {
+    Some(quantity) => Expr::Value(quantity),
+    None => Expr::Function(function, args),
+  })
+}
+
+fn evaluate(
+  function: MathFunction,
+  values: &[&Quantity],
+  options: &SimplifyOptions,
+) -> Result<Option<Quantity>, CalcError> {
+  let one = Quantity::number(1.0);
+  let number = |value: Option<f64>| value.map(Quantity::number);
+  let angle = |value: Option<f64>| value.map(|value| Quantity::new(value.to_degrees(), "deg"));
+  Ok(match (function, values) {
+    (MathFunction::Round(strategy), [value, step @ ..]) => {
+      let step = step.first().copied().unwrap_or(&one);
+      // in the unit of the step, which `round(1in, 10px)` is a multiple of
+      align(step, value, options)?
+        .map(|(step, value)| Quantity::new(round(strategy, value, step.value), step.unit))
+    }
+    (MathFunction::Mod | MathFunction::Rem, [dividend, divisor]) => {
+      align(divisor, dividend, options)?.map(|(divisor, dividend)| {
+        let remainder = modulo(function, dividend, divisor.value);
+        Quantity::new(remainder, divisor.unit)
+      })
+    }
+    // the sign of a percentage depends on what it is a percentage of
+    (MathFunction::Abs | MathFunction::Sign, [value]) if value.kind() == UnitKind::Percentage => {
+      None
+    }
+    (MathFunction::Abs, [value]) => Some(Quantity::new(value.value.abs(), value.unit.clone())),
+    (MathFunction::Sign, [value]) => {
+      // zero and NaN keep their value, including the sign of negative zero
+      let sign = if value.value == 0.0 || value.value.is_nan() {
+        value.value
+      } else {
+        value.value.signum()
+      };
+      Some(Quantity::number(sign))
+    }
+    (MathFunction::Sin, [value]) => number(angle_argument(function, value)?.map(f64::sin)),
+    (MathFunction::Cos, [value]) => number(angle_argument(function, value)?.map(f64::cos)),
+    (MathFunction::Tan, [value]) => number(angle_argument(function, value)?.map(f64::tan)),
+    (MathFunction::Asin, [value]) => angle(number_argument(function, value)?.map(f64::asin)),
+    (MathFunction::Acos, [value]) => angle(number_argument(function, value)?.map(f64::acos)),
+    (MathFunction::Atan, [value]) => angle(number_argument(function, value)?.map(f64::atan)),
+    (MathFunction::Atan2, [y, x]) => angle(align(y, x, options)?.map(|(y, x)| y.value.atan2(x))),
+    (MathFunction::Pow, [base, exponent]) => {
+      let base = number_argument(function, base)?;
+      let exponent = number_argument(function, exponent)?;
+      number(
+        base
+          .zip(exponent)
+          .map(|(base, exponent)| base.powf(exponent)),
+      )
+    }
+    (MathFunction::Sqrt, [value]) => number(number_argument(function, value)?.map(f64::sqrt)),
+    (MathFunction::Exp, [value]) => number(number_argument(function, value)?.map(f64::exp)),
+    (MathFunction::Log, [value, base @ ..]) => {
+      let value = number_argument(function, value)?;
+      let base = match base.first() {
+        Some(base) => number_argument(function, base)?,
+        None => Some(consts::E),
+      };
+      number(value.zip(base).map(|(value, base)| logarithm(value, base)))
+    }
+    (MathFunction::Hypot, [first, ..]) => {
+      let mut unit = first.unit.clone();
+      let mut squares = 0.0;
+      for value in values {
+        match align(first, value, options)? {
+          Some((first, value)) => {
+            unit = first.unit;
+            squares += value * value;
+          }
+          None => return Ok(None),
+        }
+      }
+      Some(Quantity::new(squares.sqrt(), unit))
+    }
+    _ => None,
+  })
+}
+
+/// Round `value` to a multiple of `step`
+fn round(strategy: RoundingStrategy, value: f64, step: f64) -> f64 {
+  let step = step.abs();
+  if value.is_infinite() && step.is_infinite() || step == 0.0 || step.is_nan() {
+    return f64::NAN;
+  }
+  if value.is_infinite() {
+    return value;
+  }
+  if step.is_infinite() {
+    return match strategy {
+      RoundingStrategy::Up if value > 0.0 => f64::INFINITY,
+      RoundingStrategy::Down if value < 0.0 => f64::NEG_INFINITY,
+      // zero with the sign of the value
+      _ => 0.0 * value.signum(),
+    };
+  }
+  let quotient = value / step;
+  let multiple = match strategy {
+    RoundingStrategy::Nearest => (quotient + 0.5).floor(),
+    RoundingStrategy::Up => quotient.ceil(),
+    RoundingStrategy::Down => quotient.floor(),
+    RoundingStrategy::ToZero => quotient.trunc(),
+  };
+  // `7 * 0.1` is not quite `0.7`, but `7 / 10` is
+  let inverse = 1.0 / step;
+  if inverse.fract() == 0.0 {
+    multiple / inverse
+  } else {
+    multiple * step
+  }
+}
+
+/// The remainder of `mod()`, which has the sign of the divisor, or `rem()`, which has the
+/// sign of the dividend
+fn modulo(function: MathFunction, dividend: f64, divisor: f64) -> f64 {
+  if divisor == 0.0 || dividend.is_infinite() {
+    return f64::NAN;
+  }
+  let remainder = dividend % divisor;
+  if function == MathFunction::Mod && remainder != 0.0 && (remainder < 0.0) != (divisor < 0.0) {
+    if divisor.is_infinite() {
+      f64::NAN
+    } else {
+      remainder + divisor
+    }
+  } else {
+    remainder
+  }
+}
+
+fn logarithm(value: f64, base: f64) -> f64 {
+  if base == 2.0 {
+    value.log2()
+  } else if base == 10.0 {
+    value.log10()
+  } else if base == consts::E {
+    value.ln()
+  } else {
+    value.ln() / base.ln()
+  }
+}
+
+/// Reduce `min()`, `max()` and `clamp()` as far as their arguments can be compared
+fn simplify_comparison(function: MathFunction, args: Vec<Expr>, options: &SimplifyOptions) -> Expr {
+  match function {
+    MathFunction::Min | MathFunction::Max => {
+      let keep = if function == MathFunction::Min {
+        Ordering::Less
+      } else {
+        Ordering::Greater
+      };
+      let mut result: Vec<Expr> = vec![];
+      'args: for arg in args {
+        if let Expr::Value(quantity) = &arg {
+          for existing in result.iter_mut() {
+            if let Expr::Value(existing) = existing {
+              if let Some(ordering) = compare(quantity, existing, options) {
+                if ordering == keep {
+                  *existing = quantity.clone();
+                }
+                continue 'args;
+              }
+            }
+          }
+        }
+        result.push(arg);
+      }
+      if result.len() == 1 {
+        result.pop().unwrap()
+      } else {
+        Expr::Function(function, result)
+      }
+    }
+    _ => {
+      if let [Expr::Value(min), Expr::Value(value), Expr::Value(max)] = args.as_slice() {
+        let clamped = match (compare(value, max, options), compare(min, max, options)) {
+          (Some(Ordering::Greater), Some(_)) => Some(max),
+          (Some(_), Some(_)) => Some(value),
+          _ => None,
+        };
+        // the minimum wins over the maximum
+        let clamped = clamped.and_then(|clamped| match compare(min, clamped, options)? {
+          Ordering::Greater => Some(min),
+          _ => Some(clamped),
+        });
+        if let Some(clamped) = clamped {
+          return Expr::Value(clamped.clone());
+        }
+      }
+      Expr::Function(function, args)
+    }
+  }
+}
+
+/// Serializes the expression as the inside of a `calc()`
+impl fmt::Display for Expr {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Expr::Value(quantity) if !quantity.value.is_finite() => {
+        f.write_str(non_finite_keyword(quantity.value))?;
+        if !quantity.is_number() {
+          write!(f, "*1{}", quantity.unit)?;
+        }
+        Ok(())
+      }
+      Expr::Value(quantity) => quantity.fmt(f),
+      Expr::Opaque(value) => f.write_str(value),
+      Expr::Sum(terms) => {
+        for (index, term) in terms.iter().enumerate() {
+          match term {
+            _ if index == 0 => write!(f, "{}", term)?,
+            Expr::Negate(inner) => write!(f, " - {}", Parenthesized(inner))?,
+            Expr::Value(quantity) if quantity.value.is_sign_negative() => {
+              write!(f, " - {}", -quantity.clone())?
+            }
+            // `a - 2*b` rather than `a + -2*b`
+            Expr::Product(factors) if has_negative_coefficient(factors) => {
+              write!(f, " - {}", negate(term.clone()))?
+            }
+            term => write!(f, " + {}", term)?,
+          }
+        }
+        Ok(())
+      }
+      Expr::Product(factors) => {
+        for (index, factor) in factors.iter().enumerate() {
+          match factor {
+            Expr::Invert(inner) if index == 0 => write!(f, "1/{}", Parenthesized(inner))?,
+            Expr::Invert(inner) => write!(f, "/{}", Parenthesized(inner))?,
+            factor if index == 0 => write!(f, "{}", Parenthesized(factor))?,
+            factor => write!(f, "*{}", Parenthesized(factor))?,
+          }
+        }
+        Ok(())
+      }
+      Expr::Function(function, args) => {
+        write!(f, "{}(", function.name())?;
+        match function {
+          MathFunction::Round(strategy) if *strategy != RoundingStrategy::Nearest => {
+            write!(f, "{},", strategy.name())?
+          }
+          _ => {}
+        }
+        for (index, arg) in args.iter().enumerate() {
+          if index > 0 {
+            f.write_str(",")?;
+          }
+          write!(f, "{}", arg)?;
+        }
+        f.write_str(")")
+      }
+      Expr::Negate(inner) => write!(f, "-1*{}", Parenthesized(inner)),
+      Expr::Invert(inner) => write!(f, "1/{}", Parenthesized(inner)),
+    }
+  }
+}
+
+fn has_negative_coefficient(factors: &[Expr]) -> bool {
+  matches!(factors.first(), Some(Expr::Value(quantity)) if quantity.value.is_sign_negative())
+}
+
+/// Wraps sums and products in parentheses where they are operands
+struct Parenthesized<'e>(&'e Expr);
+
+impl<'e> fmt::Display for Parenthesized<'e> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.0 {
+      expr @ (Expr::Sum(_) | Expr::Product(_) | Expr::Negate(_) | Expr::Invert(_)) => {
+        write!(f, "({})", expr)
+      }
+      // `infinity*1px`
+      expr @ Expr::Value(quantity) if !quantity.value.is_finite() && !quantity.is_number() => {
+        write!(f, "({})", expr)
+      }
+      expr => expr.fmt(f),
+    }
+  }
+}
@@ -0,0 +1,752 @@
+use crate::calc::{is_math_function, parse_math_function, Expr};
+use crate::color_mix::color_mix;
+use crate::node::{self, Function, Node};
+use crate::number::{format_number, FormatOptions};
+use crate::parser;
+use crate::quantity::Quantity;
+use crate::unit::{unit, UnitKind};
+use std::fmt;
+
+/// A CSS `<color>`
+///
+/// https://www.w3.org/TR/css-color-4/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+  /// `currentcolor`, only known once the `color` property is
+  CurrentColor,
+  Absolute(AbsoluteColor),
+}
+
+/// A color with known components in some color space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AbsoluteColor {
+  pub space: ColorSpace,
+  /// The components in the reference range of the space, `None` for a `none` component
+  pub components: [Option<f64>; 3],
+  /// From 0 to 1, `None` for `none`
+  pub alpha: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorSpace {
+  /// Red, green and blue from 0 to 1
+  Srgb,
+  /// Hue in degrees, saturation and lightness from 0 to 100
+  Hsl,
+  /// Hue in degrees, whiteness and blackness from 0 to 100
+  Hwb,
+  /// Lightness from 0 to 100, a and b around 0, roughly within ±125
+  Lab,
+  /// Lightness from 0 to 100, chroma from 0, roughly up to 150, hue in degrees
+  Lch,
+  /// Lightness from 0 to 1, a and b around 0, roughly within ±0.4
+  Oklab,
+  /// Lightness from 0 to 1, chroma from 0, roughly up to 0.4, hue in degrees
+  Oklch,
+  /// The spaces of `color()`, with components from 0 to 1
+  SrgbLinear,
+  DisplayP3,
+  A98Rgb,
+  ProphotoRgb,
+  Rec2020,
+  XyzD50,
+  XyzD65,
+}
+
+impl ColorSpace {
+  /// The name of the space: the function name, or the `color()` ident for the predefined
+  /// spaces
+  pub fn name(self) -> &'static str {
+    match self {
+      ColorSpace::Srgb => "srgb",
+      ColorSpace::Hsl => "hsl",
+      ColorSpace::Hwb => "hwb",
+      ColorSpace::Lab => "lab",
+      ColorSpace::Lch => "lch",
+      ColorSpace::Oklab => "oklab",
+      ColorSpace::Oklch => "oklch",
+      ColorSpace::SrgbLinear => "srgb-linear",
+      ColorSpace::DisplayP3 => "display-p3",
+      ColorSpace::A98Rgb => "a98-rgb",
+      ColorSpace::ProphotoRgb => "prophoto-rgb",
+      ColorSpace::Rec2020 => "rec2020",
+      ColorSpace::XyzD50 => "xyz-d50",
+      ColorSpace::XyzD65 => "xyz-d65",
+    }
+  }
+
+  /// A predefined space of `color()` from its ident, `xyz` being `xyz-d65`
+  pub fn from_predefined_name(name: &str) -> Option<ColorSpace> {
+    let name = name.to_ascii_lowercase();
+    Some(match name.as_str() {
+      "srgb" => ColorSpace::Srgb,
+      "srgb-linear" => ColorSpace::SrgbLinear,
+      "display-p3" => ColorSpace::DisplayP3,
+      "a98-rgb" => ColorSpace::A98Rgb,
+      "prophoto-rgb" => ColorSpace::ProphotoRgb,
+      "rec2020" => ColorSpace::Rec2020,
+      "xyz" | "xyz-d65" => ColorSpace::XyzD65,
+      "xyz-d50" => ColorSpace::XyzD50,
+      _ => return None,
+    })
+  }
+
+  /// Whether colors of the space are written with `color()`
+  pub fn is_predefined(self) -> bool {
+    !matches!(
+      self,
+      ColorSpace::Hsl
+        | ColorSpace::Hwb
+        | ColorSpace::Lab
+        | ColorSpace::Lch
+        | ColorSpace::Oklab
+        | ColorSpace::Oklch
+    )
+  }
+
+  /// The index of the hue component, for the polar spaces
+  pub fn hue_index(self) -> Option<usize> {
+    match self {
+      ColorSpace::Hsl | ColorSpace::Hwb => Some(0),
+      ColorSpace::Lch | ColorSpace::Oklch => Some(2),
+      _ => None,
+    }
+  }
+
+  /// What `100%` stands for in a component
+  pub(crate) fn percentage_reference(self, index: usize) -> f64 {
+    match (self, index) {
+      (ColorSpace::Hsl | ColorSpace::Hwb, _) | (ColorSpace::Lab | ColorSpace::Lch, 0) => 100.0,
+      (ColorSpace::Lab, _) => 125.0,
+      (ColorSpace::Lch, _) => 150.0,
+      (ColorSpace::Oklab | ColorSpace::Oklch, 0) => 1.0,
+      (ColorSpace::Oklab | ColorSpace::Oklch, _) => 0.4,
+      _ => 1.0,
+    }
+  }
+
+  /// The range a component is clamped to at parse time, lightness and chroma can't be
+  /// negative
+  fn component_range(self, index: usize) -> (f64, f64) {
+    match (self, index) {
+      (ColorSpace::Lab | ColorSpace::Lch, 0) => (0.0, 100.0),
+      (ColorSpace::Oklab | ColorSpace::Oklch, 0) => (0.0, 1.0),
+      (ColorSpace::Lch | ColorSpace::Oklch, 1) => (0.0, f64::INFINITY),
+      _ => (f64::NEG_INFINITY, f64::INFINITY),
+    }
+  }
+}
+
+impl Color {
+  /// Parse a value holding a single color
+  pub fn parse(value: &str) -> Option<Color> {
+    let nodes = parser::parse(value);
+    let mut nodes = nodes
+      .iter()
+      .filter(|node| !matches!(node, Node::Space(_) | Node::Comment(_)));
+    match (nodes.next(), nodes.next()) {
+      (Some(node), None) => Color::from_node(node),
+      _ => None,
+    }
+  }
+
+  /// The color a word or function node stands for, if any
+  pub fn from_node(node: &Node) -> Option<Color> {
+    match node {
+      Node::Word(word) => from_word(&word.value),
+      Node::Function(function) => from_function(function),
+      _ => None,
+    }
+  }
+
+  /// The color as a node, as serialized by `Display`
+  pub fn to_node(&self) -> Node<'static> {
+    parser::parse_serialized(&self.to_string())
+  }
+}
+
+impl AbsoluteColor {
+  pub fn new(space: ColorSpace, components: [f64; 3], alpha: f64) -> AbsoluteColor {
+    AbsoluteColor {
+      space,
+      components: components.map(Some),
+      alpha: Some(alpha),
+    }
+  }
+
+  /// An sRGB color with components from 0 to 1
+  pub fn srgb(red: f64, green: f64, blue: f64, alpha: f64) -> AbsoluteColor {
+    AbsoluteColor::new(ColorSpace::Srgb, [red, green, blue], alpha)
+  }
+
+  /// An sRGB color from its `0xRRGGBB` code
+  pub fn from_rgb_code(code: u32) -> AbsoluteColor {
+    let channel = |shift: u32| ((code >> shift) & 0xff) as f64 / 255.0;
+    AbsoluteColor::srgb(channel(16), channel(8), channel(0), 1.0)
+  }
+}
+
+/// Serializes the color in its own space, `rgb()` for sRGB colors within the gamut
+impl fmt::Display for Color {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Color::CurrentColor => f.write_str("currentcolor"),
+      Color::Absolute(color) => color.fmt(f),
+    }
+  }
+}
+
+impl fmt::Display for AbsoluteColor {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let in_rgb_gamut = self.space == ColorSpace::Srgb
+      && self
+        .components
+        .iter()
+        .flatten()
+        .all(|component| (0.0..=1.0).contains(component));
+    if in_rgb_gamut {
+      f.write_str("rgb(")?;
+    } else if self.space.is_predefined() {
+      write!(f, "color({} ", self.space.name())?;
+    } else {
+      write!(f, "{}(", self.space.name())?;
+    }
+    for (index, component) in self.components.iter().enumerate() {
+      if index > 0 {
+        f.write_str(" ")?;
+      }
+      match component {
+        None => f.write_str("none")?,
+        // float noise is far below what 8 bit channels can tell apart
+        Some(channel) if in_rgb_gamut => f.write_str(&format_number(
+          channel * 255.0,
+          &FormatOptions { precision: Some(6) },
+        ))?,
+        Some(component) => {
+          f.write_str(&format_number(*component, &FormatOptions::default()))?;
+          if matches!(self.space, ColorSpace::Hsl | ColorSpace::Hwb) && index > 0 {
+            f.write_str("%")?;
+          }
+        }
+      }
+    }
+    match self.alpha {
+      Some(1.0) => {}
+      Some(alpha) => write!(f, " / {}", format_number(alpha, &FormatOptions::default()))?,
+      None => f.write_str(" / none")?,
+    }
+    f.write_str(")")
+  }
+}
+
+/// The sRGB value of a named color, matched ASCII case-insensitively
+pub fn named_color(name: &str) -> Option<AbsoluteColor> {
+  let name = name.to_ascii_lowercase();
+  NAMED_COLORS
+    .binary_search_by(|(candidate, _)| (*candidate).cmp(name.as_str()))
+    .ok()
+    .map(|index| AbsoluteColor::from_rgb_code(NAMED_COLORS[index].1))
+}
+
+fn from_word(word: &str) -> Option<Color> {
+  if let Some(hex) = word.strip_prefix('#') {
+    return parse_hex(hex).map(Color::Absolute);
+  }
+  if word.eq_ignore_ascii_case("currentcolor") {
+    Some(Color::CurrentColor)
+  } else if word.eq_ignore_ascii_case("transparent") {
+    Some(Color::Absolute(AbsoluteColor::srgb(0.0, 0.0, 0.0, 0.0)))
+  } else {
+    named_color(word).map(Color::Absolute)
+  }
+}
+
+/// Parse the digits of `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`
+fn parse_hex(hex: &str) -> Option<AbsoluteColor> {
+  let digits = hex
+    .bytes()
+    .map(|b| (b as char).to_digit(16))
+    .collect::<Option<Vec<_>>>()?;
+  let channels = match digits.len() {
+    3 | 4 => digits.iter().map(|digit| digit * 17).collect::<Vec<_>>(),
+    6 | 8 => digits
+      .chunks(2)
+      .map(|pair| pair[0] * 16 + pair[1])
+      .collect(),
+    _ => return None,
+  };
+  let channel = |index: usize| channels.get(index).map_or(1.0, |c| *c as f64 / 255.0);
+  Some(AbsoluteColor::srgb(
+    channel(0),
+    channel(1),
+    channel(2),
+    channel(3),
+  ))
+}
+
+pub(crate) fn from_function(function: &Function) -> Option<Color> {
+  let name = function.value.to_ascii_lowercase();
+  if name == "color-mix" {
+    return color_mix(&function.nodes).map(Color::Absolute);
+  }
+  let relative;
+  let nodes = match first_argument(&function.nodes) {
+    Some((Node::Word(word), rest)) if word.value.eq_ignore_ascii_case("from") => {
+      relative = relative_arguments(&name, rest)?;
+      relative.as_slice()
+    }
+    _ => function.nodes.as_slice(),
+  };
+  if name == "color" {
+    return predefined(nodes).map(Color::Absolute);
+  }
+  let arguments = Arguments::parse(nodes)?;
+  let color = match name.as_str() {
+    "rgb" | "rgba" => rgb(&arguments),
+    "hsl" | "hsla" => hsl(&arguments),
+    "hwb" => modern(ColorSpace::Hwb, &arguments),
+    "lab" => modern(ColorSpace::Lab, &arguments),
+    "lch" => modern(ColorSpace::Lch, &arguments),
+    "oklab" => modern(ColorSpace::Oklab, &arguments),
+    "oklch" => modern(ColorSpace::Oklch, &arguments),
+    _ => None,
+  };
+  color.map(Color::Absolute)
+}
+
+/// The first node that is neither whitespace nor a comment, and the nodes after it
+pub(crate) fn first_argument<'n, 'a>(
+  nodes: &'n [Node<'a>],
+) -> Option<(&'n Node<'a>, &'n [Node<'a>])> {
+  let index = nodes
+    .iter()
+    .position(|node| !matches!(node, Node::Space(_) | Node::Comment(_)))?;
+  Some((&nodes[index], &nodes[index + 1..]))
+}
+
+/// The arguments of a relative color like `rgb(from <origin> r g calc(b / 2))` after
+/// `from`, with the origin consumed and the channel keywords replaced by its values
+///
+/// https://www.w3.org/TR/css-color-5/#relative-colors
+fn relative_arguments(name: &str, nodes: &[Node]) -> Option<Vec<Node<'static>>> {
+  let (origin, rest) = first_argument(nodes)?;
+  let origin = match Color::from_node(origin)? {
+    Color::Absolute(origin) => origin,
+    Color::CurrentColor => return None,
+  };
+  let (space, keywords) = match name {
+    "rgb" | "rgba" => (ColorSpace::Srgb, ["r", "g", "b"]),
+    "hsl" | "hsla" => (ColorSpace::Hsl, ["h", "s", "l"]),
+    "hwb" => (ColorSpace::Hwb, ["h", "w", "b"]),
+    "lab" => (ColorSpace::Lab, ["l", "a", "b"]),
+    "lch" => (ColorSpace::Lch, ["l", "c", "h"]),
+    "oklab" => (ColorSpace::Oklab, ["l", "a", "b"]),
+    "oklch" => (ColorSpace::Oklch, ["l", "c", "h"]),
+    "color" => match first_argument(rest)? {
+      (Node::Word(word), _) => match ColorSpace::from_predefined_name(&word.value)? {
+        space @ (ColorSpace::XyzD50 | ColorSpace::XyzD65) => (space, ["x", "y", "z"]),
+        space => (space, ["r", "g", "b"]),
+      },
+      _ => return None,
+    },
+    _ => return None,
+  };
+  // the channels of `rgb()` go up to 255, missing ones count as zero
+  let scale = if space == ColorSpace::Srgb && name != "color" {
+    255.0
+  } else {
+    1.0
+  };
+  let origin = origin.to_space(space);
+  let mut channels = keywords
+    .iter()
+    .zip(origin.components.iter())
+    .map(|(keyword, component)| (*keyword, component.unwrap_or(0.0) * scale))
+    .collect::<Vec<_>>();
+  channels.push(("alpha", origin.alpha.unwrap_or(0.0)));
+  Some(substitute(rest, &channels))
+}
+
+/// Replace the channel keywords in the nodes, including those in math functions
+fn substitute(nodes: &[Node], channels: &[(&str, f64)]) -> Vec<Node<'static>> {
+  nodes
+    .iter()
+    .map(|node| match node {
+      Node::Word(word) => match channels
+        .iter()
+        .find(|(keyword, _)| word.value.eq_ignore_ascii_case(keyword))
+      {
+        Some((_, value)) => Node::word(value.to_string()),
+        None => node.clone().into_owned(),
+      },
+      Node::Function(function) => Node::Function(node::Function {
+        span: function.span,
+        value: function.value.to_string().into(),
+        nodes: substitute(&function.nodes, channels),
+        unclosed: function.unclosed,
+      }),
+      node => node.clone().into_owned(),
+    })
+    .collect()
+}
+
+/// The arguments of a color function, in the legacy comma separated syntax or the modern
+/// space separated one. A `None` argument is `none`.
+struct Arguments {
+  components: Vec<Option<Quantity>>,
+  alpha: Option<Option<Quantity>>,
+  legacy: bool,
+}
+
+impl Arguments {
+  fn parse(nodes: &[Node]) -> Option<Arguments> {
+    let nodes = nodes
+      .iter()
+      .filter(|node| !matches!(node, Node::Space(_) | Node::Comment(_)))
+      .collect::<Vec<_>>();
+    let legacy = nodes
+      .iter()
+      .any(|node| matches!(node, Node::Div(div) if div.value == ","));
+    let mut arguments = Arguments {
+      components: vec![],
+      alpha: None,
+      legacy,
+    };
+    if legacy {
+      // `a, b, c` or `a, b, c, alpha`
+      for (index, node) in nodes.iter().enumerate() {
+        match node {
+          Node::Div(div) if index % 2 == 1 && div.value == "," => {}
+          node if index % 2 == 0 => arguments.components.push(Some(argument(node)??)),
+          _ => return None,
+        }
+      }
+      if nodes.len() % 2 == 0 {
+        return None;
+      }
+      if arguments.components.len() == 4 {
+        arguments.alpha = arguments.components.pop();
+      }
+    } else {
+      // `a b c` or `a b c / alpha`
+      let mut nodes = nodes.iter();
+      for node in nodes.by_ref() {
+        match node {
+          Node::Div(div) if div.value == "/" => {
+            arguments.alpha = Some(argument(nodes.next()?)?);
+            if nodes.next().is_some() {
+              return None;
+            }
+            break;
+          }
+          node => arguments.components.push(argument(node)?),
+        }
+      }
+    }
+    Some(arguments)
+  }
+}
+
+/// A number, percentage or dimension argument, or `none`. Math functions are computed.
+fn argument(node: &Node) -> Option<Option<Quantity>> {
+  match node {
+    Node::Word(word) if word.value.eq_ignore_ascii_case("none") => Some(None),
+    Node::Word(word) => {
+      let dimension = unit(&word.value)?;
+      let quantity = Quantity::from_dimension(&dimension);
+      (quantity.kind() != UnitKind::Unknown && quantity.value.is_finite()).then_some(Some(quantity))
+    }
+    Node::Function(function) if is_math_function(&function.value) => {
+      match parse_math_function(function).and_then(Expr::simplify) {
+        Ok(Expr::Value(quantity)) => Some(Some(quantity)),
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
+
+/// The alpha argument from 0 to 1, opaque when omitted
+fn alpha(arguments: &Arguments) -> Option<Option<f64>> {
+  let alpha = match &arguments.alpha {
+    None => return Some(Some(1.0)),
+    Some(None) => return Some(None),
+    Some(Some(alpha)) => alpha,
+  };
+  let value = match alpha.kind() {
+    UnitKind::Number => alpha.value,
+    UnitKind::Percentage => alpha.value / 100.0,
+    _ => return None,
+  };
+  Some(Some(value.clamp(0.0, 1.0)))
+}
+
+/// `rgb()` and `rgba()`
+///
+/// https://www.w3.org/TR/css-color-4/#rgb-functions
+fn rgb(arguments: &Arguments) -> Option<AbsoluteColor> {
+  let [red, green, blue] = <&[_; 3]>::try_from(arguments.components.as_slice()).ok()?;
+  // the legacy syntax doesn't mix numbers and percentages
+  if arguments.legacy {
+    let kinds = [red, green, blue].map(|channel| channel.as_ref().map(Quantity::kind));
+    if kinds[0] != kinds[1] || kinds[1] != kinds[2] {
+      return None;
+    }
+  }
+  let channel = |channel: &Option<Quantity>| -> Option<Option<f64>> {
+    let channel = match channel {
+      Some(channel) => channel,
+      None => return Some(None),
+    };
+    let value = match channel.kind() {
+      UnitKind::Number => channel.value / 255.0,
+      UnitKind::Percentage => channel.value / 100.0,
+      _ => return None,
+    };
+    Some(Some(value.clamp(0.0, 1.0)))
+  };
+  Some(AbsoluteColor {
+    space: ColorSpace::Srgb,
+    components: [channel(red)?, channel(green)?, channel(blue)?],
+    alpha: alpha(arguments)?,
+  })
+}
+
+/// `hsl()` and `hsla()`
+///
+/// https://www.w3.org/TR/css-color-4/#the-hsl-notation
+fn hsl(arguments: &Arguments) -> Option<AbsoluteColor> {
+  let [hue, saturation, lightness] = <&[_; 3]>::try_from(arguments.components.as_slice()).ok()?;
+  let percentage = |value: &Option<Quantity>| -> Option<Option<f64>> {
+    let value = match value {
+      Some(value) => value,
+      None => return Some(None),
+    };
+    match value.kind() {
+      UnitKind::Percentage => Some(Some(value.value.clamp(0.0, 100.0))),
+      // the legacy syntax only takes percentages
+      UnitKind::Number if !arguments.legacy => Some(Some(value.value.clamp(0.0, 100.0))),
+      _ => None,
+    }
+  };
+  Some(AbsoluteColor {
+    space: ColorSpace::Hsl,
+    components: [
+      hue_argument(hue)?,
+      percentage(saturation)?,
+      percentage(lightness)?,
+    ],
+    alpha: alpha(arguments)?,
+  })
+}
+
+/// `color()`, starting with the ident of a predefined space
+///
+/// https://www.w3.org/TR/css-color-4/#color-function
+fn predefined(nodes: &[Node]) -> Option<AbsoluteColor> {
+  let (space, rest) = first_argument(nodes)?;
+  let space = match space {
+    Node::Word(word) => ColorSpace::from_predefined_name(&word.value)?,
+    _ => return None,
+  };
+  modern(space, &Arguments::parse(rest)?)
+}
+
+/// The color functions that only have the space separated syntax, where components are
+/// numbers or percentages, and hues numbers or angles
+fn modern(space: ColorSpace, arguments: &Arguments) -> Option<AbsoluteColor> {
+  if arguments.legacy || arguments.components.len() != 3 {
+    return None;
+  }
+  let mut components = [None; 3];
+  for (index, component) in arguments.components.iter().enumerate() {
+    components[index] = if space.hue_index() == Some(index) {
+      hue_argument(component)?
+    } else {
+      match component {
+        None => None,
+        Some(component) => {
+          let value = match component.kind() {
+            UnitKind::Number => component.value,
+            UnitKind::Percentage => component.value / 100.0 * space.percentage_reference(index),
+            _ => return None,
+          };
+          let (min, max) = space.component_range(index);
+          Some(value.clamp(min, max))
+        }
+      }
+    };
+  }
+  Some(AbsoluteColor {
+    space,
+    components,
+    alpha: alpha(arguments)?,
+  })
+}
+
+/// A hue in degrees, from a number or an angle
+fn hue_argument(hue: &Option<Quantity>) -> Option<Option<f64>> {
+  let hue = match hue {
+    Some(hue) => hue,
+    None => return Some(None),
+  };
+  match hue.kind() {
+    UnitKind::Number => Some(Some(hue.value)),
+    UnitKind::Angle => Some(Some(hue.to("deg")?.value)),
+    _ => None,
+  }
+}
+
+/// The named colors, sorted by name
+///
+/// https://www.w3.org/TR/css-color-4/#named-colors
+pub(crate) const NAMED_COLORS: [(&str, u32); 148] = [
+  ("aliceblue", 0xf0f8ff),
+  ("antiquewhite", 0xfaebd7),
+  ("aqua", 0x00ffff),
+  ("aquamarine", 0x7fffd4),
+  ("azure", 0xf0ffff),
+  ("beige", 0xf5f5dc),
+  ("bisque", 0xffe4c4),
+  ("black", 0x000000),
+  ("blanchedalmond", 0xffebcd),
+  ("blue", 0x0000ff),
+  ("blueviolet", 0x8a2be2),
+  ("brown", 0xa52a2a),
+  ("burlywood", 0xdeb887),
+  ("cadetblue", 0x5f9ea0),
+  ("chartreuse", 0x7fff00),
+  ("chocolate", 0xd2691e),
+  ("coral", 0xff7f50),
+  ("cornflowerblue", 0x6495ed),
+  ("cornsilk", 0xfff8dc),
+  ("crimson", 0xdc143c),
+  ("cyan", 0x00ffff),
+  ("darkblue", 0x00008b),
+  ("darkcyan", 0x008b8b),
+  ("darkgoldenrod", 0xb8860b),
+  ("darkgray", 0xa9a9a9),
+  ("darkgreen", 0x006400),
+  ("darkgrey", 0xa9a9a9),
+  ("darkkhaki", 0xbdb76b),
+  ("darkmagenta", 0x8b008b),
+  ("darkolivegreen", 0x556b2f),
+  ("darkorange", 0xff8c00),
+  ("darkorchid", 0x9932cc),
+  ("darkred", 0x8b0000),
+  ("darksalmon", 0xe9967a),
+  ("darkseagreen", 0x8fbc8f),
+  ("darkslateblue", 0x483d8b),
+  ("darkslategray", 0x2f4f4f),
+  ("darkslategrey", 0x2f4f4f),
+  ("darkturquoise", 0x00ced1),
+  ("darkviolet", 0x9400d3),
+  ("deeppink", 0xff1493),
+  ("deepskyblue", 0x00bfff),
+  ("dimgray", 0x696969),
+  ("dimgrey", 0x696969),
+  ("dodgerblue", 0x1e90ff),
+  ("firebrick", 0xb22222),
+  ("floralwhite", 0xfffaf0),
+  ("forestgreen", 0x228b22),
+  ("fuchsia", 0xff00ff),
+  ("gainsboro", 0xdcdcdc),
+  ("ghostwhite", 0xf8f8ff),
+  ("gold", 0xffd700),
+  ("goldenrod", 0xdaa520),
+  ("gray", 0x808080),
+  ("green", 0x008000),
+  ("greenyellow", 0xadff2f),
+  ("grey", 0x808080),
+  ("honeydew", 0xf0fff0),
+  ("hotpink", 0xff69b4),
+  ("indianred", 0xcd5c5c),
+  ("indigo", 0x4b0082),
+  ("ivory", 0xfffff0),
+  ("khaki", 0xf0e68c),
+  ("lavender", 0xe6e6fa),
+  ("lavenderblush", 0xfff0f5),
+  ("lawngreen", 0x7cfc00),
+  ("lemonchiffon", 0xfffacd),
+  ("lightblue", 0xadd8e6),
+  ("lightcoral", 0xf08080),
+  ("lightcyan", 0xe0ffff),
+  ("lightgoldenrodyellow", 0xfafad2),
+  ("lightgray", 0xd3d3d3),
+  ("lightgreen", 0x90ee90),
+  ("lightgrey", 0xd3d3d3),
+  ("lightpink", 0xffb6c1),
+  ("lightsalmon", 0xffa07a),
+  ("lightseagreen", 0x20b2aa),
+  ("lightskyblue", 0x87cefa),
+  ("lightslategray", 0x778899),
+  ("lightslategrey", 0x778899),
+  ("lightsteelblue", 0xb0c4de),
+  ("lightyellow", 0xffffe0),
+  ("lime", 0x00ff00),
+  ("limegreen", 0x32cd32),
+  ("linen", 0xfaf0e6),
+  ("magenta", 0xff00ff),
+  ("maroon", 0x800000),
+  ("mediumaquamarine", 0x66cdaa),
+  ("mediumblue", 0x0000cd),
+  ("mediumorchid", 0xba55d3),
+  ("mediumpurple", 0x9370db),
+  ("mediumseagreen", 0x3cb371),
+  ("mediumslateblue", 0x7b68ee),
+  ("mediumspringgreen", 0x00fa9a),
+  ("mediumturquoise", 0x48d1cc),
+  ("mediumvioletred", 0xc71585),
+  ("midnightblue", 0x191970),
+  ("mintcream", 0xf5fffa),
+  ("mistyrose", 0xffe4e1),
+  ("moccasin", 0xffe4b5),
+  ("navajowhite", 0xffdead),
+  ("navy", 0x000080),
+  ("oldlace", 0xfdf5e6),
+  ("olive", 0x808000),
+  ("olivedrab", 0x6b8e23),
+  ("orange", 0xffa500),
+  ("orangered", 0xff4500),
+  ("orchid", 0xda70d6),
+  ("palegoldenrod", 0xeee8aa),
+  ("palegreen", 0x98fb98),
+  ("paleturquoise", 0xafeeee),
+  ("palevioletred", 0xdb7093),
+  ("papayawhip", 0xffefd5),
+  ("peachpuff", 0xffdab9),
+  ("peru", 0xcd853f),
+  ("pink", 0xffc0cb),
+  ("plum", 0xdda0dd),
+  ("powderblue", 0xb0e0e6),
+  ("purple", 0x800080),
+  ("rebeccapurple", 0x663399),
+  ("red", 0xff0000),
+  ("rosybrown", 0xbc8f8f),
+  ("royalblue", 0x4169e1),
+  ("saddlebrown", 0x8b4513),
+  ("salmon", 0xfa8072),
+  ("sandybrown", 0xf4a460),
+  ("seagreen", 0x2e8b57),
+  ("seashell", 0xfff5ee),
+  ("sienna", 0xa0522d),
+  ("silver", 0xc0c0c0),
+  ("skyblue", 0x87ceeb),
+  ("slateblue", 0x6a5acd),
+  ("slategray", 0x708090),
+  ("slategrey", 0x708090),
+  ("snow", 0xfffafa),
+  ("springgreen", 0x00ff7f),
+  ("steelblue", 0x4682b4),
+  ("tan", 0xd2b48c),
+  ("teal", 0x008080),
+  ("thistle", 0xd8bfd8),
+  ("tomato", 0xff6347),
+  ("turquoise", 0x40e0d0),
+  ("violet", 0xee82ee),
+  ("wheat", 0xf5deb3),
+  ("white", 0xffffff),
+  ("whitesmoke", 0xf5f5f5),
+  ("yellow", 0xffff00),
+  ("yellowgreen", 0x9acd32),
+];
@@ -0,0 +1,119 @@
+use crate::color::{AbsoluteColor, ColorSpace};
+use crate::color_mix::{interpolate, HueInterpolation};
+
+/// The polar spaces colors can be adjusted in, all having a lightness, a colorfulness and
+/// a hue. `Oklch` gives the most even results, `Hsl` those of Sass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AdjustmentSpace {
+  Hsl,
+  Lch,
+  #[default]
+  Oklch,
+}
+
+impl From<AdjustmentSpace> for ColorSpace {
+  fn from(space: AdjustmentSpace) -> ColorSpace {
+    match space {
+      AdjustmentSpace::Hsl => ColorSpace::Hsl,
+      AdjustmentSpace::Lch => ColorSpace::Lch,
+      AdjustmentSpace::Oklch => ColorSpace::Oklch,
+    }
+  }
+}
+
+impl AdjustmentSpace {
+  /// The indices of the lightness, colorfulness and hue components
+  fn indices(self) -> [usize; 3] {
+    match self {
+      AdjustmentSpace::Hsl => [2, 1, 0],
+      AdjustmentSpace::Lch | AdjustmentSpace::Oklch => [0, 1, 2],
+    }
+  }
+}
+
+/// Sass-like color operations. Amounts are fractions of the reference range of the
+/// component in the space, so `lighten(0.1, AdjustmentSpace::Hsl)` adds 10% of lightness
+/// like Sass does. The results are in the space of the original color, and may be out of
+/// its gamut, see [`AbsoluteColor::to_gamut`].
+impl AbsoluteColor {
+  pub fn lighten(&self, amount: f64, space: AdjustmentSpace) -> AbsoluteColor {
+    self.adjust_component(space, 0, amount)
+  }
+
+  pub fn darken(&self, amount: f64, space: AdjustmentSpace) -> AbsoluteColor {
+    self.adjust_component(space, 0, -amount)
+  }
+
+  /// Increase the saturation, or the chroma for `Lch` and `Oklch`
+  pub fn saturate(&self, amount: f64, space: AdjustmentSpace) -> AbsoluteColor {
+    self.adjust_component(space, 1, amount)
+  }
+
+  pub fn desaturate(&self, amount: f64, space: AdjustmentSpace) -> AbsoluteColor {
+    self.adjust_component(space, 1, -amount)
+  }
+
+  /// Rotate the hue by `degrees`
+  pub fn adjust_hue(&self, degrees: f64, space: AdjustmentSpace) -> AbsoluteColor {
+    let index = space.indices()[2];
+    self.adjust(space, |components| {
+      components[index] = Some((components[index].unwrap_or(0.0) + degrees).rem_euclid(360.0));
+    })
+  }
+
+  /// Remove all colorfulness, keeping the lightness of the space
+  pub fn grayscale(&self, space: AdjustmentSpace) -> AbsoluteColor {
+    let index = space.indices()[1];
+    self.adjust(space, |components| components[index] = Some(0.0))
+  }
+
+  /// Mix with another color like Sass, `weight` being the proportion of `self`. The colors
+  /// are interpolated in `space`, taking the shorter way around the hue circle.
+  pub fn mix(&self, other: &AbsoluteColor, weight: f64, space: ColorSpace) -> AbsoluteColor {
+    interpolate(self, other, 1.0 - weight, space, HueInterpolation::Shorter).to_space(self.space)
+  }
+
+  pub fn with_alpha(&self, alpha: f64) -> AbsoluteColor {
+    AbsoluteColor {
+      alpha: Some(alpha.clamp(0.0, 1.0)),
+      ..*self
+    }
+  }
+
+  /// The color with its red, green and blue channels inverted, in sRGB like Sass
+  pub fn invert(&self) -> AbsoluteColor {
+    let mut inverted = self.to_space(ColorSpace::Srgb);
+    inverted.components = inverted
+      .components
+      .map(|channel| channel.map(|channel| 1.0 - channel));
+    inverted.to_space(self.space)
+  }
+
+  /// Add `amount` of the reference range to a component, keeping it within the range
+  fn adjust_component(
+    &self,
+    space: AdjustmentSpace,
+    component: usize,
+    amount: f64,
+  ) -> AbsoluteColor {
+    let index = space.indices()[component];
+    let color_space = ColorSpace::from(space);
+    let range = color_space.percentage_reference(index);
+    // only the saturation of `hsl()` has an upper bound
+    let max = if component == 0 || space == AdjustmentSpace::Hsl {
+      range
+    } else {
+      f64::INFINITY
+    };
+    self.adjust(space, |components| {
+      let value = components[index].unwrap_or(0.0) + amount * range;
+      components[index] = Some(value.clamp(0.0, max));
+    })
+  }
+
+  fn adjust(&self, space: AdjustmentSpace, f: impl FnOnce(&mut [Option<f64>; 3])) -> AbsoluteColor {
+    let mut adjusted = self.to_space(space.into());
+    f(&mut adjusted.components);
+    adjusted.to_space(self.space)
+  }
+}
@@ -0,0 +1,119 @@
+use crate::color::{AbsoluteColor, Color, ColorSpace, NAMED_COLORS};
+use crate::node::{self, Node};
+use crate::number::{format_number, FormatOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MinifyOptions {
+  /// Never turn a color into a keyword like `red` or `transparent`, for values where
+  /// words may be identifiers rather than colors
+  pub avoid_named_colors: bool,
+  /// Allow the `#rgba` and `#rrggbbaa` hex codes, which older browsers don't support
+  pub alpha_hex: bool,
+}
+
+/// The shortest way to write a color: a hex code, a name or `rgba()`. `None` for colors
+/// that aren't 8 bit sRGB colors and so can't be written any of these ways losslessly.
+pub fn minify(color: &Color, options: &MinifyOptions) -> Option<String> {
+  let color = match color {
+    Color::Absolute(color) => color,
+    Color::CurrentColor => return None,
+  };
+  let [red, green, blue] = rgb_bytes(color)?;
+  let alpha = color.alpha?;
+  let code = u32::from_be_bytes([0, red, green, blue]);
+
+  let mut candidates = vec![];
+  if alpha == 1.0 {
+    candidates.push(hex(&[red, green, blue]));
+    if !options.avoid_named_colors {
+      candidates.extend(color_name(code).map(str::to_string));
+    }
+  } else {
+    if let Some(alpha) = to_byte(alpha).filter(|_| options.alpha_hex) {
+      candidates.push(hex(&[red, green, blue, alpha]));
+    }
+    if alpha == 0.0 && code == 0 && !options.avoid_named_colors {
+      candidates.push("transparent".to_string());
+    }
+    // the legacy syntax, which older browsers understand
+    candidates.push(format!(
+      "rgba({},{},{},{})",
+      red,
+      green,
+      blue,
+      format_number(alpha, &FormatOptions::default())
+    ));
+  }
+  // on a tie the first candidate, a hex code, wins
+  candidates.into_iter().min_by_key(String::len)
+}
+
+/// Write every color in the tree in its shortest form, see [`minify`]. Colors already
+/// written as short are left as they are.
+///
+/// Words are taken for colors wherever they can be, so `white` in `animation-name: white`
+/// would be rewritten too. Only use this on values that can hold colors.
+pub fn transform(nodes: &mut [Node], options: &MinifyOptions) {
+  for node in nodes.iter_mut() {
+    let minified = Color::from_node(node)
+      .and_then(|color| minify(&color, options))
+      .filter(|minified| minified.len() < node.to_string().len());
+    if let Some(minified) = minified {
+      *node = Node::Word(node::Word {
+        span: node.span(),
+        value: minified.into(),
+      });
+      continue;
+    }
+    match node {
+      Node::Function(function) if function.value.eq_ignore_ascii_case("url") => {}
+      Node::Function(function) => transform(&mut function.nodes, options),
+      _ => {}
+    }
+  }
+}
+
+/// The channels of a color as bytes, if it is exactly an 8 bit sRGB color
+fn rgb_bytes(color: &AbsoluteColor) -> Option<[u8; 3]> {
+  if !matches!(
+    color.space,
+    ColorSpace::Srgb | ColorSpace::Hsl | ColorSpace::Hwb
+  ) || color.components.contains(&None)
+  {
+    return None;
+  }
+  let srgb = color.to_space(ColorSpace::Srgb).components;
+  Some([to_byte(srgb[0]?)?, to_byte(srgb[1]?)?, to_byte(srgb[2]?)?])
+}
+
+/// A value from 0 to 1 as a byte, if that loses nothing
+fn to_byte(value: f64) -> Option<u8> {
+  // conversions from `hsl()` leave some float noise
+  const EPSILON: f64 = 0.000001;
+  let scaled = value * 255.0;
+  let rounded = scaled.round();
+  ((0.0..=255.0).contains(&rounded) && (scaled - rounded).abs() < EPSILON).then_some(rounded as u8)
+}
+
+/// `#rgb` or `#rgba` when every byte is a doubled digit, `#rrggbb` or `#rrggbbaa` otherwise
+fn hex(bytes: &[u8]) -> String {
+  let short = bytes.iter().all(|byte| byte >> 4 == byte & 0xf);
+  let mut hex = String::from("#");
+  for byte in bytes {
+    if short {
+      hex.push_str(&format!("{:x}", byte & 0xf));
+    } else {
+      hex.push_str(&format!("{:02x}", byte));
+    }
+  }
+  hex
+}
+
+/// The shortest name of a color, `aqua` and `cyan` being the same
+fn color_name(code: u32) -> Option<&'static str> {
+  NAMED_COLORS
+    .iter()
+    .filter(|(_, named)| *named == code)
+    .map(|(name, _)| *name)
+    .min_by_key(|name| name.len())
+}
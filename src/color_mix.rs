@@ -0,0 +1,297 @@
+use crate::color::{first_argument, from_function, AbsoluteColor, Color, ColorSpace};
+use crate::node::{Function, Node};
+use crate::unit::{unit, UnitKind};
+
+/// How hues are interpolated, going one way or the other around the hue circle
+///
+/// https://www.w3.org/TR/css-color-4/#hue-interpolation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HueInterpolation {
+  #[default]
+  Shorter,
+  Longer,
+  Increasing,
+  Decreasing,
+}
+
+impl HueInterpolation {
+  pub fn from_name(name: &str) -> Option<HueInterpolation> {
+    let name = name.to_ascii_lowercase();
+    match name.as_str() {
+      "shorter" => Some(HueInterpolation::Shorter),
+      "longer" => Some(HueInterpolation::Longer),
+      "increasing" => Some(HueInterpolation::Increasing),
+      "decreasing" => Some(HueInterpolation::Decreasing),
+      _ => None,
+    }
+  }
+
+  pub fn name(self) -> &'static str {
+    match self {
+      HueInterpolation::Shorter => "shorter",
+      HueInterpolation::Longer => "longer",
+      HueInterpolation::Increasing => "increasing",
+      HueInterpolation::Decreasing => "decreasing",
+    }
+  }
+}
+
+/// Interpolate between two colors in `space`, `progress` going from 0 for `from` to 1 for
+/// `to`. Alpha is premultiplied, and a component missing from one color is taken from the
+/// other.
+///
+/// https://www.w3.org/TR/css-color-4/#interpolation
+pub fn interpolate(
+  from: &AbsoluteColor,
+  to: &AbsoluteColor,
+  progress: f64,
+  space: ColorSpace,
+  hue_interpolation: HueInterpolation,
+) -> AbsoluteColor {
+  let (from, to) = (prepare(from, space), prepare(to, space));
+  let lerp = |from: f64, to: f64| from + (to - from) * progress;
+
+  let (from_alpha, to_alpha) = match (from.alpha, to.alpha) {
+    (Some(from), Some(to)) => (from, to),
+    (Some(alpha), None) | (None, Some(alpha)) => (alpha, alpha),
+    (None, None) => (1.0, 1.0),
+  };
+  let alpha = lerp(from_alpha, to_alpha);
+
+  let mut components = [None; 3];
+  for (index, component) in components.iter_mut().enumerate() {
+    let (from, to) = match (from.components[index], to.components[index]) {
+      (Some(from), Some(to)) => (from, to),
+      (Some(value), None) | (None, Some(value)) => (value, value),
+      (None, None) => continue,
+    };
+    *component = Some(if space.hue_index() == Some(index) {
+      let (from, to) = fix_hues(from, to, hue_interpolation);
+      lerp(from, to).rem_euclid(360.0)
+    } else {
+      let premultiplied = lerp(from * from_alpha, to * to_alpha);
+      if alpha == 0.0 {
+        premultiplied
+      } else {
+        premultiplied / alpha
+      }
+    });
+  }
+
+  AbsoluteColor {
+    space,
+    components,
+    alpha: from.alpha.or(to.alpha).map(|_| alpha),
+  }
+}
+
+/// Convert a color to the interpolation space, keeping the components that were missing
+/// missing in their analog of the new space
+///
+/// https://www.w3.org/TR/css-color-4/#interpolation-missing
+fn prepare(color: &AbsoluteColor, space: ColorSpace) -> AbsoluteColor {
+  let mut converted = color.to_space(space);
+  for (index, component) in color.components.iter().enumerate() {
+    let analog = match analog(color.space, index) {
+      Some(analog) if component.is_none() => analog,
+      _ => continue,
+    };
+    if let Some(target) = (0..3).find(|target| self::analog(space, *target) == Some(analog)) {
+      converted.components[target] = None;
+    }
+  }
+  converted
+}
+
+/// The kinds of components that carry over between color spaces when missing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Analog {
+  Red,
+  Green,
+  Blue,
+  Lightness,
+  Colorfulness,
+  Hue,
+  OpponentA,
+  OpponentB,
+}
+
+fn analog(space: ColorSpace, index: usize) -> Option<Analog> {
+  Some(match (space, index) {
+    (ColorSpace::Hsl | ColorSpace::Hwb, 0) | (ColorSpace::Lch | ColorSpace::Oklch, 2) => {
+      Analog::Hue
+    }
+    (ColorSpace::Hsl, 1) | (ColorSpace::Lch | ColorSpace::Oklch, 1) => Analog::Colorfulness,
+    (ColorSpace::Hsl, 2)
+    | (ColorSpace::Lab | ColorSpace::Lch | ColorSpace::Oklab | ColorSpace::Oklch, 0) => {
+      Analog::Lightness
+    }
+    (ColorSpace::Lab | ColorSpace::Oklab, 1) => Analog::OpponentA,
+    (ColorSpace::Lab | ColorSpace::Oklab, 2) => Analog::OpponentB,
+    (ColorSpace::Hwb | ColorSpace::XyzD50 | ColorSpace::XyzD65, _) => return None,
+    (_, 0) => Analog::Red,
+    (_, 1) => Analog::Green,
+    _ => Analog::Blue,
+  })
+}
+
+/// Adjust two hues so that going straight from one to the other goes the requested way
+fn fix_hues(from: f64, to: f64, hue_interpolation: HueInterpolation) -> (f64, f64) {
+  let (mut from, mut to) = (from.rem_euclid(360.0), to.rem_euclid(360.0));
+  let delta = to - from;
+  match hue_interpolation {
+    HueInterpolation::Shorter if delta > 180.0 => from += 360.0,
+    HueInterpolation::Shorter if delta < -180.0 => to += 360.0,
+    HueInterpolation::Longer if 0.0 < delta && delta < 180.0 => from += 360.0,
+    HueInterpolation::Longer if -180.0 < delta && delta <= 0.0 => to += 360.0,
+    HueInterpolation::Increasing if to < from => to += 360.0,
+    HueInterpolation::Decreasing if from < to => from += 360.0,
+    _ => {}
+  }
+  (from, to)
+}
+
+/// Compute the arguments of `color-mix()`, `None` unless both colors are known
+///
+/// https://www.w3.org/TR/css-color-5/#color-mix
+pub(crate) fn color_mix(nodes: &[Node]) -> Option<AbsoluteColor> {
+  let mut groups = nodes
+    .split(|node| matches!(node, Node::Div(div) if div.value == ","))
+    .collect::<Vec<_>>();
+  // the interpolation method defaults to `in oklab`
+  let (space, hue_interpolation) = match first_argument(groups.first()?)? {
+    (Node::Word(word), _) if word.value.eq_ignore_ascii_case("in") => {
+      interpolation_method(groups.remove(0))?
+    }
+    _ => (ColorSpace::Oklab, HueInterpolation::Shorter),
+  };
+  let [first, second] = <[_; 2]>::try_from(groups).ok()?;
+  let (first, first_percentage) = mix_argument(first)?;
+  let (second, second_percentage) = mix_argument(second)?;
+
+  let (first_percentage, second_percentage) = match (first_percentage, second_percentage) {
+    (None, None) => (50.0, 50.0),
+    (Some(first), None) => (first, 100.0 - first),
+    (None, Some(second)) => (100.0 - second, second),
+    (Some(first), Some(second)) => (first, second),
+  };
+  let total = first_percentage + second_percentage;
+  if total == 0.0 {
+    return None;
+  }
+  let mut mixed = interpolate(
+    &first,
+    &second,
+    second_percentage / total,
+    space,
+    hue_interpolation,
+  );
+  // percentages adding up to less than 100% make the result transparent to that extent
+  if total < 100.0 {
+    mixed.alpha = mixed.alpha.map(|alpha| alpha * total / 100.0);
+  }
+  Some(mixed)
+}
+
+/// `in <space> [<hue-interpolation-method> hue]`
+fn interpolation_method(nodes: &[Node]) -> Option<(ColorSpace, HueInterpolation)> {
+  let words = nodes
+    .iter()
+    .filter(|node| !matches!(node, Node::Space(_) | Node::Comment(_)))
+    .map(|node| match node {
+      Node::Word(word) => Some(&*word.value),
+      _ => None,
+    })
+    .collect::<Option<Vec<_>>>()?;
+  interpolation_words(words.get(1..)?)
+}
+
+/// The words of an interpolation method after `in`, like `["oklch", "longer", "hue"]`
+pub(crate) fn interpolation_words(words: &[&str]) -> Option<(ColorSpace, HueInterpolation)> {
+  let (space, hue) = match words {
+    [space] => (*space, None),
+    [space, method, hue] if hue.eq_ignore_ascii_case("hue") => (*space, Some(*method)),
+    _ => return None,
+  };
+  let space = match space.to_ascii_lowercase().as_str() {
+    "hsl" => ColorSpace::Hsl,
+    "hwb" => ColorSpace::Hwb,
+    "lab" => ColorSpace::Lab,
+    "lch" => ColorSpace::Lch,
+    "oklab" => ColorSpace::Oklab,
+    "oklch" => ColorSpace::Oklch,
+    space => ColorSpace::from_predefined_name(space)?,
+  };
+  let hue_interpolation = match hue {
+    // only polar spaces have a hue to interpolate
+    Some(method) if space.hue_index().is_some() => HueInterpolation::from_name(method)?,
+    Some(_) => return None,
+    None => HueInterpolation::Shorter,
+  };
+  Some((space, hue_interpolation))
+}
+
+/// A color and its optional percentage, in either order
+fn mix_argument(nodes: &[Node]) -> Option<(AbsoluteColor, Option<f64>)> {
+  let mut color = None;
+  let mut percentage = None;
+  for node in nodes {
+    match node {
+      Node::Space(_) | Node::Comment(_) => {}
+      Node::Word(word) if percentage.is_none() && unit(&word.value).is_some() => {
+        let dimension = unit(&word.value)?;
+        let value = dimension.value();
+        if dimension.kind() != UnitKind::Percentage || !(0.0..=100.0).contains(&value) {
+          return None;
+        }
+        percentage = Some(value);
+      }
+      node if color.is_none() => match Color::from_node(node)? {
+        Color::Absolute(absolute) => color = Some(absolute),
+        Color::CurrentColor => return None,
+      },
+      _ => return None,
+    }
+  }
+  Some((color?, percentage))
+}
+
+/// Replace the `color-mix()` and relative colors of the tree whose inputs are all known by
+/// the color they compute to. The others, like those depending on a `var()`, are left as
+/// they are.
+pub fn transform(nodes: &mut [Node]) {
+  for node in nodes.iter_mut() {
+    if let Node::Function(function) = node {
+      if is_computed_color(function) {
+        if let Some(replacement) = computed_color(function) {
+          *node = replacement;
+          continue;
+        }
+      }
+      transform(&mut function.nodes);
+    }
+  }
+}
+
+/// Whether a function computes a color out of other colors
+fn is_computed_color(function: &Function) -> bool {
+  function.value.eq_ignore_ascii_case("color-mix")
+    || matches!(
+      first_argument(&function.nodes),
+      Some((Node::Word(word), _)) if word.value.eq_ignore_ascii_case("from")
+    )
+}
+
+fn computed_color(function: &Function) -> Option<Node<'static>> {
+  let color = match from_function(function)? {
+    Color::Absolute(color) => color,
+    Color::CurrentColor => return None,
+  };
+  match Color::Absolute(color).to_node() {
+    Node::Function(mut replacement) => {
+      replacement.span = function.span;
+      Some(Node::Function(replacement))
+    }
+    _ => None,
+  }
+}
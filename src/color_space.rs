@@ -0,0 +1,614 @@
+// Conversion between color spaces and gamut mapping, after the sample code of
+// https://www.w3.org/TR/css-color-4/#color-conversion-code
+use crate::color::{AbsoluteColor, ColorSpace};
+
+type Vector = [f64; 3];
+type Matrix = [Vector; 3];
+
+/// How close to zero the chroma of a color has to be for its hue to be powerless
+const LCH_ACHROMATIC: f64 = 0.0015;
+const OKLCH_ACHROMATIC: f64 = 0.000004;
+
+impl AbsoluteColor {
+  /// The same color in another space. `none` components count as zero, except that the
+  /// color is returned as is when it already is in `space`, and hues that end up powerless
+  /// become `none`.
+  pub fn to_space(&self, space: ColorSpace) -> AbsoluteColor {
+    if self.space == space {
+      return *self;
+    }
+    let components = self.components.map(|component| component.unwrap_or(0.0));
+    let (base, target_base) = (base(self.space), base(space));
+    let base_components = to_base(self.space, components);
+    let target_components = if base == target_base {
+      base_components
+    } else {
+      from_xyz_d65(target_base, to_xyz_d65(base, base_components))
+    };
+    AbsoluteColor {
+      space,
+      components: from_base(space, target_components),
+      alpha: self.alpha,
+    }
+  }
+
+  /// Whether the color is within the gamut of `space`, which is always the case for the
+  /// unbounded spaces like `lab` and `xyz`
+  pub fn in_gamut(&self, space: ColorSpace) -> bool {
+    match gamut(space) {
+      Some(gamut) => in_rgb_gamut(&self.to_space(gamut)),
+      None => true,
+    }
+  }
+
+  /// The closest color within the gamut of `space`, found by reducing the OKLCH chroma
+  /// until clipping to the gamut doesn't make a noticeable difference
+  ///
+  /// https://www.w3.org/TR/css-color-4/#gamut-mapping
+  pub fn to_gamut(&self, space: ColorSpace) -> AbsoluteColor {
+    const JND: f64 = 0.02;
+    const EPSILON: f64 = 0.0001;
+
+    let gamut = match gamut(space) {
+      Some(gamut) => gamut,
+      None => return self.to_space(space),
+    };
+    let origin = self.to_space(ColorSpace::Oklch);
+    let lightness = origin.components[0].unwrap_or(0.0);
+    if lightness >= 1.0 {
+      let white = AbsoluteColor::new(ColorSpace::Oklab, [1.0, 0.0, 0.0], 1.0);
+      return with_alpha(white.to_space(space), self.alpha);
+    }
+    if lightness <= 0.0 {
+      let black = AbsoluteColor::new(ColorSpace::Oklab, [0.0, 0.0, 0.0], 1.0);
+      return with_alpha(black.to_space(space), self.alpha);
+    }
+    if self.in_gamut(gamut) {
+      return self.to_space(space);
+    }
+
+    let mut current = origin;
+    let mut clipped = clip(&current.to_space(gamut));
+    if delta_e_ok(&clipped, &current) < JND {
+      return clipped.to_space(space);
+    }
+    let mut min = 0.0;
+    let mut max = origin.components[1].unwrap_or(0.0);
+    let mut min_in_gamut = true;
+    while max - min > EPSILON {
+      let chroma = (min + max) / 2.0;
+      current.components[1] = Some(chroma);
+      let converted = current.to_space(gamut);
+      if min_in_gamut && in_rgb_gamut(&converted) {
+        min = chroma;
+        continue;
+      }
+      clipped = clip(&converted);
+      let delta = delta_e_ok(&clipped, &current);
+      if delta < JND {
+        if JND - delta < EPSILON {
+          break;
+        }
+        min_in_gamut = false;
+        min = chroma;
+      } else {
+        max = chroma;
+      }
+    }
+    clipped.to_space(space)
+  }
+}
+
+/// The distance between two colors in OKLab, where `0.02` is about a just noticeable
+/// difference
+pub fn delta_e_ok(left: &AbsoluteColor, right: &AbsoluteColor) -> f64 {
+  let left = left.to_space(ColorSpace::Oklab).components;
+  let right = right.to_space(ColorSpace::Oklab).components;
+  left
+    .iter()
+    .zip(right.iter())
+    .map(|(left, right)| (left.unwrap_or(0.0) - right.unwrap_or(0.0)).powi(2))
+    .sum::<f64>()
+    .sqrt()
+}
+
+fn with_alpha(mut color: AbsoluteColor, alpha: Option<f64>) -> AbsoluteColor {
+  color.alpha = alpha;
+  color
+}
+
+/// The RGB space whose gamut bounds `space`, `None` for the unbounded spaces
+fn gamut(space: ColorSpace) -> Option<ColorSpace> {
+  match space {
+    ColorSpace::Srgb | ColorSpace::Hsl | ColorSpace::Hwb => Some(ColorSpace::Srgb),
+    ColorSpace::SrgbLinear
+    | ColorSpace::DisplayP3
+    | ColorSpace::A98Rgb
+    | ColorSpace::ProphotoRgb
+    | ColorSpace::Rec2020 => Some(space),
+    ColorSpace::Lab
+    | ColorSpace::Lch
+    | ColorSpace::Oklab
+    | ColorSpace::Oklch
+    | ColorSpace::XyzD50
+    | ColorSpace::XyzD65 => None,
+  }
+}
+
+fn in_rgb_gamut(color: &AbsoluteColor) -> bool {
+  // leave some room for the float error of a round trip
+  const EPSILON: f64 = 0.000001;
+  color
+    .components
+    .iter()
+    .flatten()
+    .all(|component| (-EPSILON..=1.0 + EPSILON).contains(component))
+}
+
+fn clip(color: &AbsoluteColor) -> AbsoluteColor {
+  AbsoluteColor {
+    components: color
+      .components
+      .map(|component| component.map(|component| component.clamp(0.0, 1.0))),
+    ..*color
+  }
+}
+
+/// The space a space is a different representation of: sRGB for `hsl` and `hwb`, and
+/// the rectangular form of the polar spaces
+fn base(space: ColorSpace) -> ColorSpace {
+  match space {
+    ColorSpace::Hsl | ColorSpace::Hwb => ColorSpace::Srgb,
+    ColorSpace::Lch => ColorSpace::Lab,
+    ColorSpace::Oklch => ColorSpace::Oklab,
+    space => space,
+  }
+}
+
+fn to_base(space: ColorSpace, components: Vector) -> Vector {
+  match space {
+    ColorSpace::Hsl => hsl_to_srgb(components),
+    ColorSpace::Hwb => hwb_to_srgb(components),
+    ColorSpace::Lch | ColorSpace::Oklch => polar_to_rectangular(components),
+    _ => components,
+  }
+}
+
+fn from_base(space: ColorSpace, components: Vector) -> [Option<f64>; 3] {
+  match space {
+    ColorSpace::Hsl => srgb_to_hsl(components),
+    ColorSpace::Hwb => srgb_to_hwb(components),
+    ColorSpace::Lch => rectangular_to_polar(components, LCH_ACHROMATIC),
+    ColorSpace::Oklch => rectangular_to_polar(components, OKLCH_ACHROMATIC),
+    _ => components.map(Some),
+  }
+}
+
+fn to_xyz_d65(space: ColorSpace, components: Vector) -> Vector {
+  match space {
+    ColorSpace::Srgb => multiply(&SRGB_TO_XYZ, components.map(srgb_to_linear)),
+    ColorSpace::SrgbLinear => multiply(&SRGB_TO_XYZ, components),
+    ColorSpace::DisplayP3 => multiply(&P3_TO_XYZ, components.map(srgb_to_linear)),
+    ColorSpace::A98Rgb => multiply(&A98_TO_XYZ, components.map(a98_to_linear)),
+    ColorSpace::ProphotoRgb => multiply(
+      &D50_TO_D65,
+      multiply(&PROPHOTO_TO_XYZ_D50, components.map(prophoto_to_linear)),
+    ),
+    ColorSpace::Rec2020 => multiply(&REC2020_TO_XYZ, components.map(rec2020_to_linear)),
+    ColorSpace::XyzD50 => multiply(&D50_TO_D65, components),
+    ColorSpace::XyzD65 => components,
+    ColorSpace::Lab => multiply(&D50_TO_D65, lab_to_xyz_d50(components)),
+    ColorSpace::Oklab => oklab_to_xyz(components),
+    ColorSpace::Hsl | ColorSpace::Hwb | ColorSpace::Lch | ColorSpace::Oklch => {
+      unreachable!("polar spaces go through their base")
+    }
+  }
+}
+
+fn from_xyz_d65(space: ColorSpace, xyz: Vector) -> Vector {
+  match space {
+    ColorSpace::Srgb => multiply(&XYZ_TO_SRGB, xyz).map(srgb_from_linear),
+    ColorSpace::SrgbLinear => multiply(&XYZ_TO_SRGB, xyz),
+    ColorSpace::DisplayP3 => multiply(&XYZ_TO_P3, xyz).map(srgb_from_linear),
+    ColorSpace::A98Rgb => multiply(&XYZ_TO_A98, xyz).map(a98_from_linear),
+    ColorSpace::ProphotoRgb => {
+      multiply(&XYZ_D50_TO_PROPHOTO, multiply(&D65_TO_D50, xyz)).map(prophoto_from_linear)
+    }
+    ColorSpace::Rec2020 => multiply(&XYZ_TO_REC2020, xyz).map(rec2020_from_linear),
+    ColorSpace::XyzD50 => multiply(&D65_TO_D50, xyz),
+    ColorSpace::XyzD65 => xyz,
+    ColorSpace::Lab => xyz_d50_to_lab(multiply(&D65_TO_D50, xyz)),
+    ColorSpace::Oklab => xyz_to_oklab(xyz),
+    ColorSpace::Hsl | ColorSpace::Hwb | ColorSpace::Lch | ColorSpace::Oklch => {
+      unreachable!("polar spaces go through their base")
+    }
+  }
+}
+
+fn multiply(matrix: &Matrix, vector: Vector) -> Vector {
+  matrix.map(|row| row[0] * vector[0] + row[1] * vector[1] + row[2] * vector[2])
+}
+
+/// Apply a transfer function to the magnitude, keeping the sign of out of gamut values
+fn signed(value: f64, transfer: impl Fn(f64) -> f64) -> f64 {
+  transfer(value.abs()).copysign(value)
+}
+
+fn srgb_to_linear(value: f64) -> f64 {
+  signed(value, |value| {
+    if value <= 0.04045 {
+      value / 12.92
+    } else {
+      ((value + 0.055) / 1.055).powf(2.4)
+    }
+  })
+}
+
+fn srgb_from_linear(value: f64) -> f64 {
+  signed(value, |value| {
+    if value > 0.0031308 {
+      1.055 * value.powf(1.0 / 2.4) - 0.055
+    } else {
+      12.92 * value
+    }
+  })
+}
+
+fn a98_to_linear(value: f64) -> f64 {
+  signed(value, |value| value.powf(563.0 / 256.0))
+}
+
+fn a98_from_linear(value: f64) -> f64 {
+  signed(value, |value| value.powf(256.0 / 563.0))
+}
+
+fn prophoto_to_linear(value: f64) -> f64 {
+  signed(value, |value| {
+    if value <= 16.0 / 512.0 {
+      value / 16.0
+    } else {
+      value.powf(1.8)
+    }
+  })
+}
+
+fn prophoto_from_linear(value: f64) -> f64 {
+  signed(value, |value| {
+    if value >= 1.0 / 512.0 {
+      value.powf(1.0 / 1.8)
+    } else {
+      16.0 * value
+    }
+  })
+}
+
+const REC2020_ALPHA: f64 = 1.09929682680944;
+const REC2020_BETA: f64 = 0.018053968510807;
+
+fn rec2020_to_linear(value: f64) -> f64 {
+  signed(value, |value| {
+    if value < REC2020_BETA * 4.5 {
+      value / 4.5
+    } else {
+      ((value + REC2020_ALPHA - 1.0) / REC2020_ALPHA).powf(1.0 / 0.45)
+    }
+  })
+}
+
+fn rec2020_from_linear(value: f64) -> f64 {
+  signed(value, |value| {
+    if value > REC2020_BETA {
+      REC2020_ALPHA * value.powf(0.45) - (REC2020_ALPHA - 1.0)
+    } else {
+      4.5 * value
+    }
+  })
+}
+
+/// https://www.w3.org/TR/css-color-4/#hsl-to-rgb
+fn hsl_to_srgb([hue, saturation, lightness]: Vector) -> Vector {
+  let hue = hue.rem_euclid(360.0);
+  let (saturation, lightness) = (saturation / 100.0, lightness / 100.0);
+  let channel = |n: f64| {
+    let k = (n + hue / 30.0) % 12.0;
+    let a = saturation * lightness.min(1.0 - lightness);
+    lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+  };
+  [channel(0.0), channel(8.0), channel(4.0)]
+}
+
+/// https://www.w3.org/TR/css-color-4/#rgb-to-hsl
+fn srgb_to_hsl([red, green, blue]: Vector) -> [Option<f64>; 3] {
+  let max = red.max(green).max(blue);
+  let min = red.min(green).min(blue);
+  let lightness = (min + max) / 2.0;
+  let delta = max - min;
+  let mut hue = None;
+  let mut saturation = 0.0;
+  if delta != 0.0 {
+    saturation = if lightness == 0.0 || lightness == 1.0 {
+      0.0
+    } else {
+      (max - lightness) / lightness.min(1.0 - lightness)
+    };
+    let sector = if max == red {
+      (green - blue) / delta + if green < blue { 6.0 } else { 0.0 }
+    } else if max == green {
+      (blue - red) / delta + 2.0
+    } else {
+      (red - green) / delta + 4.0
+    };
+    hue = Some(sector * 60.0);
+  }
+  // out of gamut colors can have a negative saturation
+  if saturation < 0.0 {
+    hue = hue.map(|hue| hue + 180.0);
+    saturation = saturation.abs();
+  }
+  [
+    hue.map(|hue| hue.rem_euclid(360.0)),
+    Some(saturation * 100.0),
+    Some(lightness * 100.0),
+  ]
+}
+
+/// https://www.w3.org/TR/css-color-4/#hwb-to-rgb
+fn hwb_to_srgb([hue, whiteness, blackness]: Vector) -> Vector {
+  let (whiteness, blackness) = (whiteness / 100.0, blackness / 100.0);
+  if whiteness + blackness >= 1.0 {
+    let gray = whiteness / (whiteness + blackness);
+    return [gray; 3];
+  }
+  hsl_to_srgb([hue, 100.0, 50.0]).map(|channel| channel * (1.0 - whiteness - blackness) + whiteness)
+}
+
+fn srgb_to_hwb(srgb: Vector) -> [Option<f64>; 3] {
+  let [red, green, blue] = srgb;
+  let whiteness = red.min(green).min(blue);
+  let blackness = 1.0 - red.max(green).max(blue);
+  let hue = if whiteness + blackness >= 1.0 {
+    None
+  } else {
+    srgb_to_hsl(srgb)[0]
+  };
+  [hue, Some(whiteness * 100.0), Some(blackness * 100.0)]
+}
+
+fn polar_to_rectangular([lightness, chroma, hue]: Vector) -> Vector {
+  let hue = hue.to_radians();
+  [lightness, chroma * hue.cos(), chroma * hue.sin()]
+}
+
+fn rectangular_to_polar([lightness, a, b]: Vector, achromatic: f64) -> [Option<f64>; 3] {
+  let chroma = a.hypot(b);
+  let hue = (chroma >= achromatic).then(|| b.atan2(a).to_degrees().rem_euclid(360.0));
+  [Some(lightness), Some(chroma), hue]
+}
+
+/// The D50 white point
+const D50: Vector = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+const LAB_KAPPA: f64 = 24389.0 / 27.0;
+const LAB_EPSILON: f64 = 216.0 / 24389.0;
+
+fn xyz_d50_to_lab(xyz: Vector) -> Vector {
+  let [x, y, z] = [0, 1, 2].map(|index| {
+    let value = xyz[index] / D50[index];
+    if value > LAB_EPSILON {
+      value.cbrt()
+    } else {
+      (LAB_KAPPA * value + 16.0) / 116.0
+    }
+  });
+  [116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z)]
+}
+
+fn lab_to_xyz_d50([lightness, a, b]: Vector) -> Vector {
+  let fy = (lightness + 16.0) / 116.0;
+  let fx = a / 500.0 + fy;
+  let fz = fy - b / 200.0;
+  let inverse = |f: f64| {
+    if f.powi(3) > LAB_EPSILON {
+      f.powi(3)
+    } else {
+      (116.0 * f - 16.0) / LAB_KAPPA
+    }
+  };
+  let y = if lightness > LAB_KAPPA * LAB_EPSILON {
+    fy.powi(3)
+  } else {
+    lightness / LAB_KAPPA
+  };
+  [inverse(fx) * D50[0], y * D50[1], inverse(fz) * D50[2]]
+}
+
+fn xyz_to_oklab(xyz: Vector) -> Vector {
+  multiply(&LMS_TO_OKLAB, multiply(&XYZ_TO_LMS, xyz).map(f64::cbrt))
+}
+
+fn oklab_to_xyz(oklab: Vector) -> Vector {
+  multiply(
+    &LMS_TO_XYZ,
+    multiply(&OKLAB_TO_LMS, oklab).map(|value| value.powi(3)),
+  )
+}
+
+const SRGB_TO_XYZ: Matrix = [
+  [506752.0 / 1228815.0, 87881.0 / 245763.0, 12673.0 / 70218.0],
+  [87098.0 / 409605.0, 175762.0 / 245763.0, 12673.0 / 175545.0],
+  [7918.0 / 409605.0, 87881.0 / 737289.0, 1001167.0 / 1053270.0],
+];
+
+const XYZ_TO_SRGB: Matrix = [
+  [12831.0 / 3959.0, -329.0 / 214.0, -1974.0 / 3959.0],
+  [
+    -851781.0 / 878810.0,
+    1648619.0 / 878810.0,
+    36519.0 / 878810.0,
+  ],
+  [705.0 / 12673.0, -2585.0 / 12673.0, 705.0 / 667.0],
+];
+
+const P3_TO_XYZ: Matrix = [
+  [
+    608311.0 / 1250200.0,
+    189793.0 / 714400.0,
+    198249.0 / 1000160.0,
+  ],
+  [
+    35783.0 / 156275.0,
+    247089.0 / 357200.0,
+    198249.0 / 2500400.0,
+  ],
+  [0.0, 32229.0 / 714400.0, 5220557.0 / 5000800.0],
+];
+
+const XYZ_TO_P3: Matrix = [
+  [
+    446124.0 / 178915.0,
+    -333277.0 / 357830.0,
+    -72051.0 / 178915.0,
+  ],
+  [-14852.0 / 17905.0, 63121.0 / 35810.0, 423.0 / 17905.0],
+  [11844.0 / 330415.0, -50337.0 / 660830.0, 316169.0 / 330415.0],
+];
+
+const A98_TO_XYZ: Matrix = [
+  [
+    573536.0 / 994567.0,
+    263643.0 / 1420810.0,
+    187206.0 / 994567.0,
+  ],
+  [
+    591459.0 / 1989134.0,
+    6239551.0 / 9945670.0,
+    374412.0 / 4972835.0,
+  ],
+  [
+    53769.0 / 1989134.0,
+    351524.0 / 4972835.0,
+    4929758.0 / 4972835.0,
+  ],
+];
+
+const XYZ_TO_A98: Matrix = [
+  [
+    1829569.0 / 896150.0,
+    -506331.0 / 896150.0,
+    -308931.0 / 896150.0,
+  ],
+  [
+    -851781.0 / 878810.0,
+    1648619.0 / 878810.0,
+    36519.0 / 878810.0,
+  ],
+  [
+    16779.0 / 1248040.0,
+    -147721.0 / 1248040.0,
+    1266979.0 / 1248040.0,
+  ],
+];
+
+const PROPHOTO_TO_XYZ_D50: Matrix = [
+  [0.7977666449006423, 0.13518129740053308, 0.0313477341283922],
+  [0.2880748288194013, 0.711835234241873, 0.00008993693872564],
+  [0.0, 0.0, 0.8251046025104602],
+];
+
+const XYZ_D50_TO_PROPHOTO: Matrix = [
+  [
+    1.3457868816471583,
+    -0.25557208737979464,
+    -0.05110186497554526,
+  ],
+  [-0.5446307051249019, 1.5082477428451468, 0.02052744743642139],
+  [0.0, 0.0, 1.2119675456389452],
+];
+
+const REC2020_TO_XYZ: Matrix = [
+  [
+    63426534.0 / 99577255.0,
+    20160776.0 / 139408157.0,
+    47086771.0 / 278816314.0,
+  ],
+  [
+    26158966.0 / 99577255.0,
+    472592308.0 / 697040785.0,
+    8267143.0 / 139408157.0,
+  ],
+  [0.0, 19567812.0 / 697040785.0, 295819943.0 / 278816314.0],
+];
+
+const XYZ_TO_REC2020: Matrix = [
+  [
+    30757411.0 / 17917100.0,
+    -6372589.0 / 17917100.0,
+    -4539589.0 / 17917100.0,
+  ],
+  [
+    -19765991.0 / 29648200.0,
+    47925759.0 / 29648200.0,
+    467509.0 / 29648200.0,
+  ],
+  [
+    792561.0 / 44930125.0,
+    -1921689.0 / 44930125.0,
+    42328811.0 / 44930125.0,
+  ],
+];
+
+/// Bradford chromatic adaptation
+const D65_TO_D50: Matrix = [
+  [
+    1.0479297925449969,
+    0.022946870601609652,
+    -0.05019226628920524,
+  ],
+  [
+    0.02962780877005599,
+    0.9904344267538799,
+    -0.017073799063418826,
+  ],
+  [
+    -0.009243040646204504,
+    0.015055191490298152,
+    0.7518742814281371,
+  ],
+];
+
+const D50_TO_D65: Matrix = [
+  [0.955473421488075, -0.02309845494876471, 0.06325924320057072],
+  [
+    -0.0283697093338637,
+    1.0099953980813041,
+    0.021041441191917323,
+  ],
+  [
+    0.012314014864481998,
+    -0.020507649298898964,
+    1.330365926242124,
+  ],
+];
+
+const XYZ_TO_LMS: Matrix = [
+  [0.819022437996703, 0.3619062600528904, -0.1288737815209879],
+  [0.0329836539323885, 0.9292868615863434, 0.0361446663506424],
+  [0.0481771893596242, 0.2642395317527308, 0.6335478284694309],
+];
+
+const LMS_TO_OKLAB: Matrix = [
+  [0.210454268309314, 0.7936177747023054, -0.0040720430116193],
+  [1.9779985324311684, -2.42859224204858, 0.450593709617411],
+  [0.0259040424655478, 0.7827717124575296, -0.8086757549230774],
+];
+
+const LMS_TO_XYZ: Matrix = [
+  [1.2268798758459243, -0.5578149944602171, 0.2813910456659647],
+  [-0.0405757452148008, 1.112286803280317, -0.0717110580655164],
+  [-0.0763729366746601, -0.4214933324022432, 1.5869240198367816],
+];
+
+const OKLAB_TO_LMS: Matrix = [
+  [1.0, 0.3963377773761749, 0.2158037573099136],
+  [1.0, -0.1055613458156586, -0.0638541728258133],
+  [1.0, -0.0894841775298119, -1.2914855480194092],
+];
@@ -0,0 +1,162 @@
+use crate::color::{AbsoluteColor, ColorSpace};
+
+/// The WCAG 2 conformance levels for contrast
+///
+/// https://www.w3.org/TR/WCAG21/#contrast-minimum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WcagLevel {
+  AA,
+  AAA,
+}
+
+impl WcagLevel {
+  /// The smallest contrast ratio the level accepts, large text being at least 18pt, or
+  /// 14pt and bold
+  pub fn min_ratio(self, large_text: bool) -> f64 {
+    match (self, large_text) {
+      (WcagLevel::AA, false) => 4.5,
+      (WcagLevel::AA, true) | (WcagLevel::AAA, true) => 3.0,
+      (WcagLevel::AAA, false) => 7.0,
+    }
+  }
+}
+
+impl AbsoluteColor {
+  /// The color seen when painting this color over `background`, in sRGB
+  pub fn composite_over(&self, background: &AbsoluteColor) -> AbsoluteColor {
+    let source = clipped_srgb(self);
+    let background = clipped_srgb(background);
+    let (alpha, background_alpha) = (source.alpha.unwrap_or(0.0), background.alpha.unwrap_or(0.0));
+    let result_alpha = alpha + background_alpha * (1.0 - alpha);
+    let mut components = [Some(0.0); 3];
+    if result_alpha > 0.0 {
+      for (index, component) in components.iter_mut().enumerate() {
+        let channel = source.components[index].unwrap_or(0.0);
+        let background_channel = background.components[index].unwrap_or(0.0);
+        *component = Some(
+          (channel * alpha + background_channel * background_alpha * (1.0 - alpha)) / result_alpha,
+        );
+      }
+    }
+    AbsoluteColor {
+      space: ColorSpace::Srgb,
+      components,
+      alpha: Some(result_alpha),
+    }
+  }
+}
+
+/// The WCAG 2 relative luminance of a color, from 0 for black to 1 for white. Alpha is
+/// ignored, composite the color first if it matters.
+///
+/// https://www.w3.org/TR/WCAG21/#dfn-relative-luminance
+pub fn relative_luminance(color: &AbsoluteColor) -> f64 {
+  let [red, green, blue] = clipped_srgb(color).components.map(|channel| {
+    let channel = channel.unwrap_or(0.0);
+    if channel <= 0.03928 {
+      channel / 12.92
+    } else {
+      ((channel + 0.055) / 1.055).powf(2.4)
+    }
+  });
+  0.2126 * red + 0.7152 * green + 0.0722 * blue
+}
+
+/// The WCAG 2 contrast ratio of text over a background, from 1 to 21. Translucent text is
+/// composited over the background first, and a translucent background over white.
+///
+/// https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio
+pub fn contrast_ratio(foreground: &AbsoluteColor, background: &AbsoluteColor) -> f64 {
+  let (foreground, background) = composite_pair(foreground, background);
+  let foreground = relative_luminance(&foreground);
+  let background = relative_luminance(&background);
+  let (lighter, darker) = if foreground > background {
+    (foreground, background)
+  } else {
+    (background, foreground)
+  };
+  (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Whether text of the `foreground` color over `background` meets the WCAG 2 level
+pub fn meets_wcag(
+  foreground: &AbsoluteColor,
+  background: &AbsoluteColor,
+  level: WcagLevel,
+  large_text: bool,
+) -> bool {
+  contrast_ratio(foreground, background) >= level.min_ratio(large_text)
+}
+
+/// The APCA lightness contrast `Lc` of text over a background, about 106 for black text on
+/// white and -108 for white text on black. Positive for dark text on a light background,
+/// negative for light text on a dark one, 0 when too close to tell apart.
+///
+/// APCA 0.0.98G-4g, https://github.com/Myndex/apca-w3
+pub fn apca_contrast(text: &AbsoluteColor, background: &AbsoluteColor) -> f64 {
+  const NORMAL_BACKGROUND: f64 = 0.56;
+  const NORMAL_TEXT: f64 = 0.57;
+  const REVERSE_TEXT: f64 = 0.62;
+  const REVERSE_BACKGROUND: f64 = 0.65;
+  const SCALE: f64 = 1.14;
+  const OFFSET: f64 = 0.027;
+  const LOW_CLIP: f64 = 0.1;
+  const MIN_DELTA_Y: f64 = 0.0005;
+
+  let (text, background) = composite_pair(text, background);
+  let (text, background) = (apca_luminance(&text), apca_luminance(&background));
+  if (background - text).abs() < MIN_DELTA_Y {
+    return 0.0;
+  }
+  let contrast = if background > text {
+    let contrast = (background.powf(NORMAL_BACKGROUND) - text.powf(NORMAL_TEXT)) * SCALE;
+    if contrast < LOW_CLIP {
+      0.0
+    } else {
+      contrast - OFFSET
+    }
+  } else {
+    let contrast = (background.powf(REVERSE_BACKGROUND) - text.powf(REVERSE_TEXT)) * SCALE;
+    if contrast > -LOW_CLIP {
+      0.0
+    } else {
+      contrast + OFFSET
+    }
+  };
+  contrast * 100.0
+}
+
+/// The screen luminance estimate of APCA, with its soft clamp of near blacks
+fn apca_luminance(color: &AbsoluteColor) -> f64 {
+  const BLACK_THRESHOLD: f64 = 0.022;
+  const BLACK_CLAMP: f64 = 1.414;
+
+  let [red, green, blue] = clipped_srgb(color)
+    .components
+    .map(|channel| channel.unwrap_or(0.0).powf(2.4));
+  let luminance = 0.2126729 * red + 0.7151522 * green + 0.072175 * blue;
+  if luminance < BLACK_THRESHOLD {
+    luminance + (BLACK_THRESHOLD - luminance).powf(BLACK_CLAMP)
+  } else {
+    luminance
+  }
+}
+
+/// Make both colors opaque: the background over white, then the foreground over it
+fn composite_pair(
+  foreground: &AbsoluteColor,
+  background: &AbsoluteColor,
+) -> (AbsoluteColor, AbsoluteColor) {
+  let white = AbsoluteColor::srgb(1.0, 1.0, 1.0, 1.0);
+  let background = background.composite_over(&white);
+  (foreground.composite_over(&background), background)
+}
+
+/// The color in sRGB, clipped to its gamut
+fn clipped_srgb(color: &AbsoluteColor) -> AbsoluteColor {
+  let mut srgb = color.to_space(ColorSpace::Srgb);
+  srgb.components = srgb
+    .components
+    .map(|channel| channel.map(|channel| channel.clamp(0.0, 1.0)));
+  srgb
+}
@@ -0,0 +1,656 @@
+use crate::calc::{is_math_function, serialize, value, Expr};
+use crate::color::{Color, ColorSpace};
+use crate::color_mix::{interpolation_words, HueInterpolation};
+use crate::node::{significant, Function, Node};
+use crate::unit::{unit, UnitKind};
+use std::fmt;
+
+/// A `linear-gradient()`, `radial-gradient()` or `conic-gradient()`, or one of their
+/// `repeating-` and vendor prefixed forms
+///
+/// https://www.w3.org/TR/css-images-4/#gradients
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient<'n, 'a> {
+  pub kind: GradientKind,
+  pub repeating: bool,
+  /// The vendor prefix of forms like `-webkit-linear-gradient()`, where the direction is
+  /// the side the gradient starts from and angles go counterclockwise from the right
+  pub prefix: Option<VendorPrefix>,
+  /// The color space of `in <color-space>`, `None` when omitted
+  pub interpolation: Option<(ColorSpace, HueInterpolation)>,
+  pub items: Vec<GradientItem<'n, 'a>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VendorPrefix {
+  Webkit,
+  Moz,
+  O,
+}
+
+impl VendorPrefix {
+  /// The prefix as written before a function name, like `-webkit-`
+  pub fn name(self) -> &'static str {
+    match self {
+      VendorPrefix::Webkit => "-webkit-",
+      VendorPrefix::Moz => "-moz-",
+      VendorPrefix::O => "-o-",
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GradientKind {
+  /// The direction of the gradient line, `None` for the default `to bottom`
+  Linear(Option<LineDirection>),
+  Radial(RadialGradient),
+  Conic(ConicGradient),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineDirection {
+  Angle(Expr),
+  /// `to right`, `to top left`, at least one of the two is set
+  Side {
+    horizontal: Option<HorizontalSide>,
+    vertical: Option<VerticalSide>,
+  },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HorizontalSide {
+  Left,
+  Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerticalSide {
+  Top,
+  Bottom,
+}
+
+/// The ending shape of a radial gradient, everything is optional as in the syntax
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RadialGradient {
+  pub shape: Option<RadialShape>,
+  pub size: Option<RadialSize>,
+  pub position: Option<Position>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RadialShape {
+  Circle,
+  Ellipse,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RadialSize {
+  Extent(RadialExtent),
+  /// The radius of a circle
+  Circle(Expr),
+  /// The horizontal and vertical radii of an ellipse
+  Ellipse(Expr, Expr),
+}
+
+/// The size keywords, the prefixed `contain` and `cover` are `closest-side` and
+/// `farthest-corner`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RadialExtent {
+  ClosestSide,
+  ClosestCorner,
+  FarthestSide,
+  FarthestCorner,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConicGradient {
+  /// The angle of `from <angle>`
+  pub angle: Option<Expr>,
+  pub position: Option<Position>,
+}
+
+/// A `<position>` as written, one to four keywords and offsets
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position(pub Vec<PositionComponent>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PositionComponent {
+  Keyword(PositionKeyword),
+  Value(Expr),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionKeyword {
+  Left,
+  Center,
+  Right,
+  Top,
+  Bottom,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GradientItem<'n, 'a> {
+  Stop(ColorStop<'n, 'a>),
+  /// A transition hint between two stops, the position where their colors are half mixed
+  Hint(Expr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorStop<'n, 'a> {
+  /// The color node, which may be a `var()` or anything else that isn't known yet
+  pub color: &'n Node<'a>,
+  /// No position, one, or the two of a stop spanning from one to the other
+  pub positions: Vec<Expr>,
+}
+
+impl ColorStop<'_, '_> {
+  /// The color of the stop, if it is known
+  pub fn color(&self) -> Option<Color> {
+    Color::from_node(self.color)
+  }
+}
+
+impl<'n, 'a> Gradient<'n, 'a> {
+  /// Parse a gradient function, `None` for any other function or an invalid gradient
+  pub fn parse(function: &'n Function<'a>) -> Option<Self> {
+    if function.unclosed {
+      return None;
+    }
+    let name = function.value.to_ascii_lowercase();
+    let (prefix, name) = match [VendorPrefix::Webkit, VendorPrefix::Moz, VendorPrefix::O]
+      .into_iter()
+      .find_map(|prefix| Some((prefix, name.strip_prefix(prefix.name())?)))
+    {
+      Some((prefix, name)) => (Some(prefix), name),
+      None => (None, name.as_str()),
+    };
+    let prefixed = prefix.is_some();
+    let (repeating, name) = match name.strip_prefix("repeating-") {
+      Some(name) => (true, name),
+      None => (false, name),
+    };
+    let arguments = function
+      .nodes
+      .split(|node| matches!(node, Node::Div(div) if div.value == ","))
+      .map(significant)
+      .collect::<Vec<_>>();
+
+    // only an `in <color-space>` clause leaves the prelude empty
+    let first = arguments.first().filter(|first| !first.is_empty())?;
+    let (interpolation, prelude) = split_interpolation(first)?;
+    if prefixed && interpolation.is_some() {
+      return None;
+    }
+    let mut consumed = 1;
+    let kind = match name {
+      "linear-gradient" if prelude.is_empty() => Some(GradientKind::Linear(None)),
+      "linear-gradient" => {
+        line_direction(&prelude, prefixed).map(|direction| GradientKind::Linear(Some(direction)))
+      }
+      "radial-gradient" if prelude.is_empty() => {
+        Some(GradientKind::Radial(RadialGradient::default()))
+      }
+      "radial-gradient" if prefixed => {
+        // `-webkit-radial-gradient(<position>, <shape> <size>, ...)`, both optional
+        match position(&prelude) {
+          Some(position) => {
+            let (shape, size) = match arguments.get(1).and_then(|next| shape_size(next, true)) {
+              Some(shape_size) => {
+                consumed += 1;
+                shape_size
+              }
+              None => (None, None),
+            };
+            Some(GradientKind::Radial(RadialGradient {
+              shape,
+              size,
+              position: Some(position),
+            }))
+          }
+          None => shape_size(&prelude, true).map(|(shape, size)| {
+            GradientKind::Radial(RadialGradient {
+              shape,
+              size,
+              position: None,
+            })
+          }),
+        }
+      }
+      "radial-gradient" => radial(&prelude).map(GradientKind::Radial),
+      "conic-gradient" if prefixed => return None,
+      "conic-gradient" => conic(&prelude).map(GradientKind::Conic),
+      _ => return None,
+    };
+    let kind = match kind {
+      Some(kind) => kind,
+      // the first argument is a color stop
+      None if interpolation.is_none() => {
+        consumed = 0;
+        match name {
+          "linear-gradient" => GradientKind::Linear(None),
+          "radial-gradient" => GradientKind::Radial(RadialGradient::default()),
+          _ => GradientKind::Conic(ConicGradient::default()),
+        }
+      }
+      None => return None,
+    };
+
+    let items = arguments[consumed..]
+      .iter()
+      .map(|tokens| item(tokens, matches!(kind, GradientKind::Conic(_))))
+      .collect::<Option<Vec<_>>>()?;
+    // hints only go between two stops
+    let is_hint = |item: Option<&GradientItem>| matches!(item, Some(GradientItem::Hint(_)));
+    if items.is_empty()
+      || is_hint(items.first())
+      || is_hint(items.last())
+      || items
+        .windows(2)
+        .any(|pair| matches!(pair, [GradientItem::Hint(_), GradientItem::Hint(_)]))
+    {
+      return None;
+    }
+    Some(Gradient {
+      kind,
+      repeating,
+      prefix,
+      interpolation,
+      items,
+    })
+  }
+
+  /// Parse a gradient node, `None` for any other node
+  pub fn from_node(node: &'n Node<'a>) -> Option<Self> {
+    match node {
+      Node::Function(function) => Gradient::parse(function),
+      _ => None,
+    }
+  }
+}
+
+/// The lowercase value of a word that is not a number
+fn keyword(node: &Node) -> Option<String> {
+  match node {
+    Node::Word(word) if unit(&word.value).is_none() => Some(word.value.to_ascii_lowercase()),
+    _ => None,
+  }
+}
+
+fn is_keyword(node: &Node, name: &str) -> bool {
+  matches!(node, Node::Word(word) if word.value.eq_ignore_ascii_case(name))
+}
+
+/// Take the `in <color-space> [<hue-interpolation-method> hue]` out of the first argument,
+/// at its start or end. `None` if it is invalid.
+#[allow(clippy::type_complexity)]
+fn split_interpolation<'n, 'a>(
+  tokens: &[&'n Node<'a>],
+) -> Option<(Option<(ColorSpace, HueInterpolation)>, Vec<&'n Node<'a>>)> {
+  let start = match tokens.iter().position(|token| is_keyword(token, "in")) {
+    Some(start) => start,
+    None => return Some((None, tokens.to_vec())),
+  };
+  let length = match tokens.get(start + 3) {
+    Some(token) if is_keyword(token, "hue") => 3,
+    _ => 1,
+  };
+  let end = start + 1 + length;
+  if start != 0 && end != tokens.len() {
+    return None;
+  }
+  let words = tokens
+    .get(start + 1..end)?
+    .iter()
+    .map(|token| keyword(token))
+    .collect::<Option<Vec<_>>>()?;
+  let words = words.iter().map(String::as_str).collect::<Vec<_>>();
+  let interpolation = interpolation_words(&words)?;
+  let mut rest = tokens[..start].to_vec();
+  rest.extend_from_slice(&tokens[end..]);
+  Some((Some(interpolation), rest))
+}
+
+/// A stop or hint position: a length-percentage, or an angle-percentage in a conic
+/// gradient, where a plain `0` is allowed too. It may also be a `var()` and the like.
+fn stop_position(node: &Node, conic: bool) -> Option<Expr> {
+  let expr = match node {
+    Node::Function(function) if !is_math_function(&function.value) => {
+      return Some(Expr::Opaque(node.to_string()))
+    }
+    Node::Placeholder(placeholder) => return Some(Expr::Opaque(placeholder.value.to_string())),
+    node => value(node)?,
+  };
+  match &expr {
+    Expr::Value(quantity) => {
+      let kind = quantity.kind();
+      let dimension = if conic {
+        kind == UnitKind::Angle
+      } else {
+        kind.is_length()
+      };
+      (dimension || kind == UnitKind::Percentage || (quantity.is_number() && quantity.value == 0.0))
+        .then_some(expr)
+    }
+    _ => Some(expr),
+  }
+}
+
+/// An angle, where a plain `0` is allowed too
+fn angle(node: &Node) -> Option<Expr> {
+  let expr = value(node)?;
+  match &expr {
+    Expr::Value(quantity) => (quantity.kind() == UnitKind::Angle
+      || (quantity.is_number() && quantity.value == 0.0))
+      .then_some(expr),
+    _ => Some(expr),
+  }
+}
+
+/// Whether a node may be the color of a stop: a known color, or a function or placeholder
+/// whose value isn't known yet
+fn is_color(node: &Node) -> bool {
+  match node {
+    Node::Word(_) => Color::from_node(node).is_some(),
+    Node::Function(function) => !is_math_function(&function.value),
+    Node::Placeholder(_) => true,
+    _ => false,
+  }
+}
+
+fn item<'n, 'a>(tokens: &[&'n Node<'a>], conic: bool) -> Option<GradientItem<'n, 'a>> {
+  let (color, positions) = match tokens {
+    [color, positions @ ..] if is_color(color) => (*color, positions),
+    [positions @ .., color] if is_color(color) => (*color, positions),
+    [hint] => return stop_position(hint, conic).map(GradientItem::Hint),
+    _ => return None,
+  };
+  if positions.len() > 2 {
+    return None;
+  }
+  let positions = positions
+    .iter()
+    .map(|position| stop_position(position, conic))
+    .collect::<Option<Vec<_>>>()?;
+  Some(GradientItem::Stop(ColorStop { color, positions }))
+}
+
+/// `<angle>` or `to <side-or-corner>`, where prefixed gradients omit the `to`
+fn line_direction(tokens: &[&Node], prefixed: bool) -> Option<LineDirection> {
+  if let [token] = tokens {
+    if let Some(angle) = angle(token) {
+      return Some(LineDirection::Angle(angle));
+    }
+  }
+  let sides = match tokens {
+    [to, sides @ ..] if !prefixed && is_keyword(to, "to") => sides,
+    sides if prefixed => sides,
+    _ => return None,
+  };
+  if sides.is_empty() || sides.len() > 2 {
+    return None;
+  }
+  let mut horizontal = None;
+  let mut vertical = None;
+  for side in sides {
+    match keyword(side)?.as_str() {
+      "left" if horizontal.is_none() => horizontal = Some(HorizontalSide::Left),
+      "right" if horizontal.is_none() => horizontal = Some(HorizontalSide::Right),
+      "top" if vertical.is_none() => vertical = Some(VerticalSide::Top),
+      "bottom" if vertical.is_none() => vertical = Some(VerticalSide::Bottom),
+      _ => return None,
+    }
+  }
+  Some(LineDirection::Side {
+    horizontal,
+    vertical,
+  })
+}
+
+/// `[<shape> || <size>]? [at <position>]?`
+fn radial(tokens: &[&Node]) -> Option<RadialGradient> {
+  let (shape_size_tokens, position) = match tokens.iter().position(|token| is_keyword(token, "at"))
+  {
+    Some(at) => (&tokens[..at], Some(position(&tokens[at + 1..])?)),
+    None => (tokens, None),
+  };
+  let (shape, size) = if shape_size_tokens.is_empty() {
+    (None, None)
+  } else {
+    shape_size(shape_size_tokens, false)?
+  };
+  Some(RadialGradient {
+    shape,
+    size,
+    position,
+  })
+}
+
+fn shape_size(
+  tokens: &[&Node],
+  prefixed: bool,
+) -> Option<(Option<RadialShape>, Option<RadialSize>)> {
+  let mut shape = None;
+  let mut extent = None;
+  let mut lengths = Vec::new();
+  for token in tokens {
+    let keyword = keyword(token);
+    match keyword.as_deref() {
+      Some("circle") if shape.is_none() => shape = Some(RadialShape::Circle),
+      Some("ellipse") if shape.is_none() => shape = Some(RadialShape::Ellipse),
+      Some(name) if extent.is_none() => {
+        extent = Some(match name {
+          "closest-side" => RadialExtent::ClosestSide,
+          "closest-corner" => RadialExtent::ClosestCorner,
+          "farthest-side" => RadialExtent::FarthestSide,
+          "farthest-corner" => RadialExtent::FarthestCorner,
+          "contain" if prefixed => RadialExtent::ClosestSide,
+          "cover" if prefixed => RadialExtent::FarthestCorner,
+          _ => return None,
+        })
+      }
+      None if lengths.len() < 2 => lengths.push(value(token)?),
+      _ => return None,
+    }
+  }
+  let size = match (extent, lengths.len()) {
+    (Some(extent), 0) => Some(RadialSize::Extent(extent)),
+    (None, 0) => None,
+    (None, 1) if shape != Some(RadialShape::Ellipse) => {
+      // a circle can't be sized by a percentage
+      if matches!(&lengths[0], Expr::Value(quantity) if quantity.kind() == UnitKind::Percentage) {
+        return None;
+      }
+      Some(RadialSize::Circle(lengths.remove(0)))
+    }
+    (None, 2) if shape != Some(RadialShape::Circle) => {
+      let vertical = lengths.remove(1);
+      Some(RadialSize::Ellipse(lengths.remove(0), vertical))
+    }
+    _ => return None,
+  };
+  Some((shape, size))
+}
+
+/// `[from <angle>]? [at <position>]?`
+fn conic(tokens: &[&Node]) -> Option<ConicGradient> {
+  let (angle, rest) = match tokens {
+    [from, value, rest @ ..] if is_keyword(from, "from") => (Some(angle(value)?), rest),
+    rest => (None, rest),
+  };
+  let position = match rest {
+    [] => None,
+    [at, rest @ ..] if is_keyword(at, "at") => Some(position(rest)?),
+    _ => return None,
+  };
+  Some(ConicGradient { angle, position })
+}
+
+/// One to four position keywords and offsets
+fn position(tokens: &[&Node]) -> Option<Position> {
+  if tokens.is_empty() || tokens.len() > 4 {
+    return None;
+  }
+  tokens
+    .iter()
+    .map(|token| match keyword(token) {
+      Some(keyword) => Some(PositionComponent::Keyword(match keyword.as_str() {
+        "left" => PositionKeyword::Left,
+        "center" => PositionKeyword::Center,
+        "right" => PositionKeyword::Right,
+        "top" => PositionKeyword::Top,
+        "bottom" => PositionKeyword::Bottom,
+        _ => return None,
+      })),
+      None => value(token).map(PositionComponent::Value),
+    })
+    .collect::<Option<Vec<_>>>()
+    .map(Position)
+}
+
+/// A value in a gradient, `calc()` around unresolved calculations
+struct Value<'e>(&'e Expr);
+
+impl fmt::Display for Value<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.0 {
+      Expr::Opaque(value) => f.write_str(value),
+      expr => f.write_str(&serialize(expr)),
+    }
+  }
+}
+
+impl fmt::Display for Gradient<'_, '_> {
+  /// The gradient function, with the vendor prefix it was written with
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(prefix) = self.prefix {
+      f.write_str(prefix.name())?;
+    }
+    if self.repeating {
+      f.write_str("repeating-")?;
+    }
+    // the arguments before the stops, and the words of the last of them
+    let mut arguments = Vec::new();
+    let mut prelude = Vec::new();
+    let name = match &self.kind {
+      GradientKind::Linear(direction) => {
+        match direction {
+          Some(LineDirection::Angle(angle)) => prelude.push(Value(angle).to_string()),
+          Some(LineDirection::Side {
+            horizontal,
+            vertical,
+          }) => {
+            let mut sides = if self.prefix.is_some() {
+              Vec::new()
+            } else {
+              vec!["to"]
+            };
+            sides.extend(vertical.map(|side| match side {
+              VerticalSide::Top => "top",
+              VerticalSide::Bottom => "bottom",
+            }));
+            sides.extend(horizontal.map(|side| match side {
+              HorizontalSide::Left => "left",
+              HorizontalSide::Right => "right",
+            }));
+            prelude.push(sides.join(" "));
+          }
+          None => {}
+        }
+        "linear-gradient"
+      }
+      GradientKind::Radial(radial) => {
+        let mut shape_size = Vec::new();
+        shape_size.extend(radial.shape.map(|shape| {
+          match shape {
+            RadialShape::Circle => "circle",
+            RadialShape::Ellipse => "ellipse",
+          }
+          .to_string()
+        }));
+        match &radial.size {
+          Some(RadialSize::Extent(extent)) => shape_size.push(
+            match extent {
+              RadialExtent::ClosestSide => "closest-side",
+              RadialExtent::ClosestCorner => "closest-corner",
+              RadialExtent::FarthestSide => "farthest-side",
+              RadialExtent::FarthestCorner => "farthest-corner",
+            }
+            .to_string(),
+          ),
+          Some(RadialSize::Circle(radius)) => shape_size.push(Value(radius).to_string()),
+          Some(RadialSize::Ellipse(horizontal, vertical)) => {
+            shape_size.push(format!("{} {}", Value(horizontal), Value(vertical)))
+          }
+          None => {}
+        }
+        match &radial.position {
+          // the prefixed syntax puts the position in an argument of its own, first
+          Some(position) if self.prefix.is_some() => {
+            arguments.push(position.to_string());
+            prelude = shape_size;
+          }
+          Some(position) => {
+            shape_size.push(format!("at {}", position));
+            prelude.push(shape_size.join(" "));
+          }
+          None if !shape_size.is_empty() => prelude.push(shape_size.join(" ")),
+          None => {}
+        }
+        "radial-gradient"
+      }
+      GradientKind::Conic(conic) => {
+        if let Some(angle) = &conic.angle {
+          prelude.push(format!("from {}", Value(angle)));
+        }
+        if let Some(position) = &conic.position {
+          prelude.push(format!("at {}", position));
+        }
+        "conic-gradient"
+      }
+    };
+    if let Some((space, hue_interpolation)) = self.interpolation {
+      let mut method = format!("in {}", space.name());
+      if hue_interpolation != HueInterpolation::Shorter {
+        method.push_str(&format!(" {} hue", hue_interpolation.name()));
+      }
+      prelude.push(method);
+    }
+    if !prelude.is_empty() {
+      arguments.push(prelude.join(" "));
+    }
+    for item in &self.items {
+      arguments.push(match item {
+        GradientItem::Stop(stop) => {
+          let mut stop_text = stop.color.to_string();
+          for position in &stop.positions {
+            stop_text.push_str(&format!(" {}", Value(position)));
+          }
+          stop_text
+        }
+        GradientItem::Hint(hint) => Value(hint).to_string(),
+      });
+    }
+    write!(f, "{}({})", name, arguments.join(", "))
+  }
+}
+
+impl fmt::Display for Position {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (index, component) in self.0.iter().enumerate() {
+      if index > 0 {
+        f.write_str(" ")?;
+      }
+      match component {
+        PositionComponent::Keyword(keyword) => f.write_str(match keyword {
+          PositionKeyword::Left => "left",
+          PositionKeyword::Center => "center",
+          PositionKeyword::Right => "right",
+          PositionKeyword::Top => "top",
+          PositionKeyword::Bottom => "bottom",
+        })?,
+        PositionComponent::Value(value) => write!(f, "{}", Value(value))?,
+      }
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,300 @@
+// Rewriting of the gradients of old stylesheets, `-webkit-gradient()` and the vendor
+// prefixed gradient functions, into the standard syntax.
+
+use crate::calc::Expr;
+use crate::gradient::{
+  ColorStop, Gradient, GradientItem, GradientKind, HorizontalSide, LineDirection, Position,
+  PositionComponent, RadialGradient, RadialShape, RadialSize, VerticalSide,
+};
+use crate::node::{significant, Function, Node};
+use crate::parser;
+use crate::quantity::Quantity;
+use crate::unit::{unit, UnitKind};
+
+impl<'n, 'a> Gradient<'n, 'a> {
+  /// The gradient in the standard syntax. The directions of prefixed linear gradients are
+  /// the side they start from and their angles go counterclockwise from the right, so they
+  /// are reversed. `None` when the angle can't be computed, like `calc(var(--a))`.
+  pub fn to_standard(&self) -> Option<Gradient<'n, 'a>> {
+    let mut standard = self.clone();
+    if self.prefix.is_none() {
+      return Some(standard);
+    }
+    standard.prefix = None;
+    if let GradientKind::Linear(Some(direction)) = &self.kind {
+      standard.kind = GradientKind::Linear(match direction {
+        LineDirection::Angle(Expr::Value(angle)) => {
+          let degrees = match angle.kind() {
+            UnitKind::Angle => angle.to("deg")?.value,
+            _ if angle.is_number() && angle.value == 0.0 => 0.0,
+            _ => return None,
+          };
+          Some(LineDirection::Angle(Expr::Value(Quantity::new(
+            (90.0 - degrees).rem_euclid(360.0),
+            "deg",
+          ))))
+        }
+        LineDirection::Angle(_) => return None,
+        LineDirection::Side {
+          horizontal,
+          vertical,
+        } => match (horizontal, vertical) {
+          // `to bottom` is the default
+          (None, Some(VerticalSide::Top)) => None,
+          _ => Some(LineDirection::Side {
+            horizontal: horizontal.map(|side| match side {
+              HorizontalSide::Left => HorizontalSide::Right,
+              HorizontalSide::Right => HorizontalSide::Left,
+            }),
+            vertical: vertical.map(|side| match side {
+              VerticalSide::Top => VerticalSide::Bottom,
+              VerticalSide::Bottom => VerticalSide::Top,
+            }),
+          }),
+        },
+      });
+    }
+    Some(standard)
+  }
+}
+
+/// Convert `-webkit-gradient(linear, <point>, <point>, <stop>...)` and
+/// `-webkit-gradient(radial, <point>, <radius>, <point>, <radius>, <stop>...)` to a
+/// standard gradient. `None` when the standard syntax can't express it: linear gradients
+/// that are neither horizontal, vertical nor from corner to corner, and radial gradients
+/// whose circles aren't concentric.
+///
+/// A line from corner to corner becomes `to bottom right` and the like, which only renders
+/// the same in a square box. In other boxes the standard gradient line is at a different
+/// angle, so that the corners it doesn't go through share the middle color.
+///
+/// https://webkit.org/blog/175/introducing-css-gradients/
+pub fn webkit_gradient<'n, 'a>(function: &'n Function<'a>) -> Option<Gradient<'n, 'a>> {
+  if !function.value.eq_ignore_ascii_case("-webkit-gradient") || function.unclosed {
+    return None;
+  }
+  let arguments = function
+    .nodes
+    .split(|node| matches!(node, Node::Div(div) if div.value == ","))
+    .map(significant)
+    .collect::<Vec<_>>();
+  let (kind, rest) = arguments.split_first()?;
+  let kind = match kind.as_slice() {
+    [Node::Word(word)] => word.value.to_ascii_lowercase(),
+    _ => return None,
+  };
+  let (kind, start, end, stops) = match (kind.as_str(), rest) {
+    ("linear", [from, to, stops @ ..]) => {
+      let ([x1, y1], [x2, y2]) = (point(from)?, point(to)?);
+      let corner =
+        |value: &Quantity| value.unit == "%" && (value.value == 0.0 || value.value == 100.0);
+      let (direction, start, end) = if x1 == x2 {
+        (None, y1, y2)
+      } else if y1 == y2 {
+        let direction = LineDirection::Side {
+          horizontal: Some(HorizontalSide::Right),
+          vertical: None,
+        };
+        (Some(direction), x1, x2)
+      } else if [&x1, &y1, &x2, &y2].into_iter().all(corner) {
+        let direction = LineDirection::Side {
+          horizontal: Some(if x2.value > x1.value {
+            HorizontalSide::Right
+          } else {
+            HorizontalSide::Left
+          }),
+          vertical: Some(if y2.value > y1.value {
+            VerticalSide::Bottom
+          } else {
+            VerticalSide::Top
+          }),
+        };
+        (
+          Some(direction),
+          Quantity::new(0.0, "%"),
+          Quantity::new(100.0, "%"),
+        )
+      } else {
+        return None;
+      };
+      (GradientKind::Linear(direction), start, end, stops)
+    }
+    ("radial", [from, from_radius, to, to_radius, stops @ ..]) => {
+      let center = point(from)?;
+      if point(to)? != center {
+        return None;
+      }
+      let (start, end) = (radius(from_radius)?, radius(to_radius)?);
+      let size = if start.value > end.value {
+        start.clone()
+      } else {
+        end.clone()
+      };
+      // a circle from the center is sized by its end, the stops are in percentages of it
+      let (start, end) = if start.value == 0.0 {
+        (Quantity::new(0.0, "%"), Quantity::new(100.0, "%"))
+      } else {
+        (start, end)
+      };
+      let position = (center != [Quantity::new(50.0, "%"), Quantity::new(50.0, "%")]).then(|| {
+        Position(
+          center
+            .into_iter()
+            .map(|value| PositionComponent::Value(Expr::Value(value)))
+            .collect(),
+        )
+      });
+      let radial = RadialGradient {
+        shape: Some(RadialShape::Circle),
+        size: Some(RadialSize::Circle(Expr::Value(size))),
+        position,
+      };
+      (GradientKind::Radial(radial), start, end, stops)
+    }
+    _ => return None,
+  };
+  if start.unit != end.unit {
+    return None;
+  }
+
+  let mut stops = stops
+    .iter()
+    .map(|stop| color_stop(stop))
+    .collect::<Option<Vec<_>>>()?;
+  if stops.is_empty() {
+    return None;
+  }
+  // the stops are sorted by offset, and the standard ones have to go forward
+  stops.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+  if end.value < start.value {
+    stops.reverse();
+  }
+  let last = stops.len() - 1;
+  let items = stops
+    .into_iter()
+    .enumerate()
+    .map(|(index, (offset, color))| {
+      let position = Quantity::new(
+        start.value + offset * (end.value - start.value),
+        &*start.unit,
+      );
+      // the first and last stops are at the ends of the gradient line by default
+      let default =
+        (index == 0 && position.value == 0.0) || (index == last && position.value == 100.0);
+      let positions = if default && position.unit == "%" {
+        Vec::new()
+      } else {
+        vec![Expr::Value(position)]
+      };
+      GradientItem::Stop(ColorStop { color, positions })
+    })
+    .collect();
+  Some(Gradient {
+    kind,
+    repeating: false,
+    prefix: None,
+    interpolation: None,
+    items,
+  })
+}
+
+/// Rewrite `-webkit-gradient()` and the prefixed gradients of the tree in the standard
+/// syntax. Those that can't be converted are left as they are, and the corner to corner
+/// `-webkit-gradient()` only matches in square boxes, see [`webkit_gradient`].
+pub fn transform(nodes: &mut [Node]) {
+  for node in nodes.iter_mut() {
+    if let Node::Function(function) = node {
+      let standard = match webkit_gradient(function) {
+        Some(gradient) => Some(gradient),
+        None => Gradient::parse(function)
+          .filter(|gradient| gradient.prefix.is_some())
+          .and_then(|gradient| gradient.to_standard()),
+      };
+      match standard.map(|gradient| gradient.to_string()) {
+        Some(standard) => {
+          let span = function.span;
+          let mut replacement = parser::parse_serialized(&standard);
+          if let Node::Function(replacement) = &mut replacement {
+            replacement.span = span;
+          }
+          *node = replacement;
+        }
+        None => transform(&mut function.nodes),
+      }
+    }
+  }
+}
+
+/// A point of `-webkit-gradient()`, with the keywords as percentages and numbers in pixels
+fn point(tokens: &[&Node]) -> Option<[Quantity; 2]> {
+  let [x, y] = tokens else {
+    return None;
+  };
+  let coordinate = |node: &Node, start: &str, end: &str| {
+    let value = match node {
+      Node::Word(word) => &word.value,
+      _ => return None,
+    };
+    let percentage = match value.to_ascii_lowercase() {
+      keyword if keyword == start => Some(0.0),
+      keyword if keyword == "center" => Some(50.0),
+      keyword if keyword == end => Some(100.0),
+      _ => None,
+    };
+    if let Some(percentage) = percentage {
+      return Some(Quantity::new(percentage, "%"));
+    }
+    let dimension = unit(value)?;
+    match dimension.kind() {
+      UnitKind::Number => Some(Quantity::new(dimension.value(), "px")),
+      UnitKind::Percentage => Some(Quantity::from_dimension(&dimension)),
+      _ => None,
+    }
+  };
+  Some([
+    coordinate(x, "left", "right")?,
+    coordinate(y, "top", "bottom")?,
+  ])
+}
+
+/// A radius of `-webkit-gradient()`, a number of pixels
+fn radius(tokens: &[&Node]) -> Option<Quantity> {
+  match tokens {
+    [Node::Word(word)] => {
+      let dimension = unit(&word.value)?;
+      (dimension.kind() == UnitKind::Number && dimension.value() >= 0.0)
+        .then(|| Quantity::new(dimension.value(), "px"))
+    }
+    _ => None,
+  }
+}
+
+/// `from(<color>)`, `to(<color>)` or `color-stop(<offset>, <color>)`, with the offset
+/// from 0 to 1
+fn color_stop<'n, 'a>(tokens: &[&'n Node<'a>]) -> Option<(f64, &'n Node<'a>)> {
+  let function = match tokens {
+    [Node::Function(function)] => function,
+    _ => return None,
+  };
+  let arguments = function
+    .nodes
+    .split(|node| matches!(node, Node::Div(div) if div.value == ","))
+    .map(significant)
+    .collect::<Vec<_>>();
+  let arguments = arguments.iter().map(Vec::as_slice).collect::<Vec<_>>();
+  let name = function.value.to_ascii_lowercase();
+  match (name.as_str(), arguments.as_slice()) {
+    ("from", [[color]]) => Some((0.0, *color)),
+    ("to", [[color]]) => Some((1.0, *color)),
+    ("color-stop", [[Node::Word(offset)], [color]]) => {
+      let dimension = unit(&offset.value)?;
+      let offset = match dimension.kind() {
+        UnitKind::Number => dimension.value(),
+        UnitKind::Percentage => dimension.value() / 100.0,
+        _ => return None,
+      };
+      Some((offset, *color))
+    }
+    _ => None,
+  }
+}
@@ -0,0 +1,132 @@
+use crate::span::Span;
+use crate::tokenizer::{PlaceholderPattern, Token, TokenType, Tokenizer, TokenizerOptions};
+use std::ops::Range;
+
+/// Replace the text covered by `span` with `new_text`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TextEdit<'e> {
+  pub span: Span,
+  pub new_text: &'e str,
+}
+
+impl<'e> TextEdit<'e> {
+  pub fn new(span: Span, new_text: &'e str) -> TextEdit<'e> {
+    TextEdit { span, new_text }
+  }
+
+  /// Apply the edit to the value it was made against
+  pub fn apply(&self, value: &str) -> String {
+    let mut result = String::with_capacity(value.len() - self.span.len() + self.new_text.len());
+    result.push_str(&value[..self.span.start]);
+    result.push_str(self.new_text);
+    result.push_str(&value[self.span.end..]);
+    result
+  }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Retokenized<'a> {
+  pub tokens: Vec<Token<'a>>,
+  /// The range of `tokens` that was produced by re-tokenizing
+  pub changed: Range<usize>,
+  /// The range of the previous token list that `changed` replaces
+  pub replaced: Range<usize>,
+}
+
+/// Re-tokenize `new_value`, the result of applying `edit` to the value `old_tokens` were produced from.
+///
+/// The tokenizer only carries its position between tokens, so tokenizing restarts at the
+/// first token that could have looked at the edited text, and stops as soon as it lands on
+/// the start of a token past the edit again. Strings and comments opened or closed by the
+/// edit keep the damaged region growing until the tokens line up.
+pub fn retokenize<'a>(
+  old_tokens: &[Token],
+  edit: &TextEdit,
+  new_value: &'a str,
+  options: TokenizerOptions<'_>,
+) -> Retokenized<'a> {
+  let edit_start = edit.span.start;
+  let old_edit_end = edit.span.end;
+  let new_edit_end = edit_start + edit.new_text.len();
+
+  // a token reads up to and including the byte at its end offset
+  let mut restart = old_tokens.partition_point(|token| token.span.end < edit_start);
+  // a word is cut short where a placeholder begins, even if the placeholder is what changed
+  while restart > 0
+    && restart < old_tokens.len()
+    && old_tokens[restart].kind == TokenType::Placeholder
+    && old_tokens[restart - 1].span.end == old_tokens[restart].span.start
+  {
+    restart -= 1;
+  }
+  if let Some(PlaceholderPattern::Delimited { prefix, .. }) = options.placeholder {
+    // whether a prefix starts a placeholder depends on a suffix anywhere after it
+    if let Some(first) = prefix.as_bytes().first() {
+      if let Some(index) = old_tokens[..restart]
+        .iter()
+        .position(|token| token.text.as_bytes().contains(first))
+      {
+        restart = index;
+      }
+    }
+  }
+  let restart_pos = old_tokens
+    .get(restart)
+    .map_or(edit_start, |token| token.span.start);
+
+  let mut tokens: Vec<Token<'a>> = old_tokens[..restart]
+    .iter()
+    .map(|token| rebase(token, new_value, |offset| offset))
+    .collect();
+
+  let shift = |offset: usize| offset - old_edit_end + new_edit_end;
+  let processor = Tokenizer::with_options(new_value, options);
+  processor.seek(restart_pos);
+  let mut resync = old_tokens.len();
+  while !processor.end_of_file() {
+    let pos = processor.position();
+    // the line comment check looks one byte back, into the edited text
+    let looks_back =
+      options.dialect.line_comments() && new_value.as_bytes().get(pos) == Some(&b'/');
+    if pos > new_edit_end || (pos == new_edit_end && !looks_back) {
+      let old_pos = pos - new_edit_end + old_edit_end;
+      let rest = &old_tokens[restart..];
+      if let Ok(index) = rest.binary_search_by_key(&old_pos, |token| token.span.start) {
+        resync = restart + index;
+        break;
+      }
+    }
+    tokens.push(processor.next_token());
+  }
+  let changed = restart..tokens.len();
+
+  tokens.extend(
+    old_tokens[resync..]
+      .iter()
+      .map(|token| rebase(token, new_value, shift)),
+  );
+
+  Retokenized {
+    tokens,
+    changed,
+    replaced: restart..resync,
+  }
+}
+
+/// Move a token to `new_value`, mapping its offsets with `shift`
+fn rebase<'a>(token: &Token, new_value: &'a str, shift: impl Fn(usize) -> usize) -> Token<'a> {
+  // comment content skips the opening `/*` or `//`
+  let text_start = match token.kind {
+    TokenType::Comment | TokenType::LineComment => token.span.start + 2,
+    _ => token.span.start,
+  };
+  let text_start = shift(text_start).min(new_value.len());
+  let text_end = (text_start + token.text.len()).min(new_value.len());
+  Token::new(
+    token.kind,
+    &new_value[text_start..text_end],
+    shift(token.span.start),
+    shift(token.span.end),
+  )
+  .with_unclosed(token.unclosed)
+}
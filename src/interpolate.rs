@@ -0,0 +1,713 @@
+// Interpolation of values for animations and transitions: lengths, percentages, numbers,
+// colors, shadow lists and transform lists, falling back to a discrete flip halfway.
+//
+// https://www.w3.org/TR/css-values-4/#combining-values
+
+use crate::calc::{is_math_function, serialize, value, Expr};
+use crate::color::{AbsoluteColor, Color, ColorSpace};
+use crate::color_mix::{self, HueInterpolation};
+use crate::node::{is_blank, significant, Node};
+use crate::parser;
+use crate::quantity::Quantity;
+use crate::transform::{Matrix, TransformFunction, TransformList};
+use crate::unit::UnitKind;
+
+/// The value `progress` of the way from `from` to `to`, 0 giving `from` and 1 giving `to`.
+/// Values that can't be blended, like two different keywords, flip from one to the other
+/// at 0.5.
+pub fn interpolate(from: &[Node], to: &[Node], progress: f64) -> Vec<Node<'static>> {
+  let interpolated = match (TransformList::parse(from), TransformList::parse(to)) {
+    (Some(from), Some(to)) => transform_list(&from, &to, progress),
+    _ => match (shadow_list(from), shadow_list(to)) {
+      (Some(from), Some(to)) => shadows(&from, &to, progress),
+      _ => nodes(from, to, progress),
+    },
+  };
+  match interpolated {
+    Some(value) => parser::parse(&value)
+      .into_iter()
+      .map(Node::into_owned)
+      .collect(),
+    None => {
+      let discrete = if progress < 0.5 { from } else { to };
+      discrete.iter().cloned().map(Node::into_owned).collect()
+    }
+  }
+}
+
+/// Blend the values node by node, keeping the whitespace of `from`
+fn nodes(from: &[Node], to: &[Node], progress: f64) -> Option<String> {
+  let (significant_from, significant_to) = (significant(from), significant(to));
+  if significant_from.is_empty() || significant_from.len() != significant_to.len() {
+    return None;
+  }
+  let mut blended = significant_from
+    .iter()
+    .zip(&significant_to)
+    .map(|(from, to)| node(from, to, progress))
+    .collect::<Option<Vec<_>>>()?
+    .into_iter();
+  let mut result = String::new();
+  for node in from {
+    match node {
+      node if is_blank(node) => result.push_str(&node.to_string()),
+      _ => result.push_str(&blended.next()?),
+    }
+  }
+  Some(result)
+}
+
+fn node(from: &Node, to: &Node, progress: f64) -> Option<String> {
+  let from_text = from.to_string();
+  if from_text == to.to_string() {
+    return Some(from_text);
+  }
+  if let (Some(from), Some(to)) = (value(from), value(to)) {
+    return blend(&from, &to, progress).map(|blended| serialize(&blended));
+  }
+  if let (Some(Color::Absolute(from)), Some(Color::Absolute(to))) =
+    (Color::from_node(from), Color::from_node(to))
+  {
+    return Some(color(&from, &to, progress).to_string());
+  }
+  match (from, to) {
+    (Node::Function(from), Node::Function(to))
+      if from.value.eq_ignore_ascii_case(&to.value) && !is_math_function(&from.value) =>
+    {
+      let arguments = nodes(&from.nodes, &to.nodes, progress)?;
+      Some(format!("{}({})", from.value, arguments))
+    }
+    _ => None,
+  }
+}
+
+/// Whether a calculation only holds values, and no `var()` that could be anything
+fn is_computable(expr: &Expr) -> bool {
+  match expr {
+    Expr::Value(_) => true,
+    Expr::Opaque(_) => false,
+    Expr::Negate(inner) | Expr::Invert(inner) => is_computable(inner),
+    Expr::Sum(terms) | Expr::Product(terms) | Expr::Function(_, terms) => {
+      terms.iter().all(is_computable)
+    }
+  }
+}
+
+/// Blend two numbers, dimensions or calculations of the same type. Lengths and percentages
+/// that only resolve at layout time are blended in a `calc()`.
+fn blend(from: &Expr, to: &Expr, progress: f64) -> Option<Expr> {
+  if let (Expr::Value(from), Expr::Value(to)) = (from, to) {
+    // a unitless zero is a zero length
+    let zero = |quantity: &Quantity| quantity.is_number() && quantity.value == 0.0;
+    let (from, to) = match (zero(from), zero(to)) {
+      (true, false) => (Quantity::new(0.0, &*to.unit), to.clone()),
+      (false, true) => (from.clone(), Quantity::new(0.0, &*from.unit)),
+      _ => (from.clone(), to.clone()),
+    };
+    if let Some(to) = to.to(&from.unit) {
+      let value = from.value + (to.value - from.value) * progress;
+      return Some(Expr::Value(Quantity::new(value, from.unit)));
+    }
+    let mixable =
+      |quantity: &Quantity| quantity.kind().is_length() || quantity.kind() == UnitKind::Percentage;
+    if !mixable(&from) || !mixable(&to) {
+      return None;
+    }
+  }
+  if !is_computable(from) || !is_computable(to) {
+    return None;
+  }
+  let weighted = |expr: &Expr, weight: f64| {
+    Expr::Product(vec![expr.clone(), Expr::Value(Quantity::number(weight))])
+  };
+  Expr::Sum(vec![weighted(from, 1.0 - progress), weighted(to, progress)])
+    .simplify()
+    .ok()
+}
+
+/// Blend two colors in Oklab, the result is in sRGB when both colors are
+fn color(from: &AbsoluteColor, to: &AbsoluteColor, progress: f64) -> AbsoluteColor {
+  let blended = color_mix::interpolate(
+    from,
+    to,
+    progress,
+    ColorSpace::Oklab,
+    HueInterpolation::Shorter,
+  );
+  let legacy = |color: &AbsoluteColor| {
+    matches!(
+      color.space,
+      ColorSpace::Srgb | ColorSpace::Hsl | ColorSpace::Hwb
+    )
+  };
+  if legacy(from) && legacy(to) && blended.in_gamut(ColorSpace::Srgb) {
+    let mut srgb = blended.to_space(ColorSpace::Srgb);
+    srgb.components = srgb
+      .components
+      .map(|channel| channel.map(|channel| channel.clamp(0.0, 1.0)));
+    srgb
+  } else {
+    blended
+  }
+}
+
+#[derive(Debug, Clone)]
+struct Shadow {
+  inset: bool,
+  color: AbsoluteColor,
+  /// The offsets, blur radius and spread distance, two to four of them
+  lengths: Vec<Expr>,
+}
+
+/// A `box-shadow` or `text-shadow` list whose colors are all known, empty for `none`
+fn shadow_list(nodes: &[Node]) -> Option<Vec<Shadow>> {
+  if let [Node::Word(word)] = significant(nodes).as_slice() {
+    if word.value.eq_ignore_ascii_case("none") {
+      return Some(Vec::new());
+    }
+  }
+  nodes
+    .split(|node| matches!(node, Node::Div(div) if div.value == ","))
+    .map(|shadow| {
+      let mut inset = false;
+      let mut color = None;
+      let mut lengths = Vec::new();
+      for node in significant(shadow) {
+        if let Some(length) = value(node) {
+          let is_length = match &length {
+            Expr::Value(quantity) if quantity.is_number() => quantity.value == 0.0,
+            Expr::Value(quantity) => quantity.kind().is_length(),
+            _ => true,
+          };
+          if !is_length || lengths.len() == 4 {
+            return None;
+          }
+          lengths.push(length);
+          continue;
+        }
+        match node {
+          Node::Word(word) if word.value.eq_ignore_ascii_case("inset") && !inset => inset = true,
+          node if color.is_none() => match Color::from_node(node)? {
+            Color::Absolute(absolute) => color = Some(absolute),
+            Color::CurrentColor => return None,
+          },
+          _ => return None,
+        }
+      }
+      if lengths.len() < 2 {
+        return None;
+      }
+      Some(Shadow {
+        inset,
+        color: color?,
+        lengths,
+      })
+    })
+    .collect()
+}
+
+/// Blend shadow lists shadow by shadow, the shorter list is padded with transparent
+/// shadows that have no offset
+///
+/// https://www.w3.org/TR/css-backgrounds-3/#box-shadow
+fn shadows(from: &[Shadow], to: &[Shadow], progress: f64) -> Option<String> {
+  if from.is_empty() && to.is_empty() {
+    return Some("none".to_string());
+  }
+  let zero = || Expr::Value(Quantity::number(0.0));
+  let padding = |other: &Shadow| Shadow {
+    inset: other.inset,
+    color: AbsoluteColor::srgb(0.0, 0.0, 0.0, 0.0),
+    lengths: vec![zero(); other.lengths.len()],
+  };
+  let count = from.len().max(to.len());
+  let mut blended = Vec::with_capacity(count);
+  for index in 0..count {
+    let (mut from, mut to) = match (from.get(index), to.get(index)) {
+      (Some(from), Some(to)) => (from.clone(), to.clone()),
+      (Some(from), None) => (from.clone(), padding(from)),
+      (None, Some(to)) => (padding(to), to.clone()),
+      (None, None) => unreachable!(),
+    };
+    if from.inset != to.inset {
+      return None;
+    }
+    // a missing blur or spread is 0
+    let length_count = from.lengths.len().max(to.lengths.len());
+    from.lengths.resize(length_count, zero());
+    to.lengths.resize(length_count, zero());
+
+    let mut parts = Vec::new();
+    if from.inset {
+      parts.push("inset".to_string());
+    }
+    for (from, to) in from.lengths.iter().zip(&to.lengths) {
+      parts.push(serialize(&blend(from, to, progress)?));
+    }
+    parts.push(color(&from.color, &to.color, progress).to_string());
+    blended.push(parts.join(" "));
+  }
+  Some(blended.join(", "))
+}
+
+/// Blend transform lists function by function where they match, and the rest of them as
+/// matrices. `none` and the end of the shorter list stand for the identity functions of the
+/// other list.
+///
+/// https://www.w3.org/TR/css-transforms-2/#interpolation-of-transform-functions
+fn transform_list(from: &TransformList, to: &TransformList, progress: f64) -> Option<String> {
+  let (mut from, mut to) = (from.0.clone(), to.0.clone());
+  if from.is_empty() && to.is_empty() {
+    return Some("none".to_string());
+  }
+  if from.len() < to.len() {
+    from.extend(to[from.len()..].iter().map(identity));
+  } else {
+    to.extend(from[to.len()..].iter().map(identity));
+  }
+  let mut blended = Vec::new();
+  let mut index = 0;
+  while index < from.len() {
+    match transform_function(&from[index], &to[index], progress) {
+      Some(function) => blended.push(function),
+      None => break,
+    }
+    index += 1;
+  }
+  if index < from.len() {
+    blended.push(matrices(&from[index..], &to[index..], progress)?);
+  }
+  Some(TransformList(blended).to_string())
+}
+
+/// The function of the same kind that leaves everything in place
+fn identity(function: &TransformFunction) -> TransformFunction {
+  let zero = || Expr::Value(Quantity::number(0.0));
+  // `0deg` so that blending from an identity gives degrees
+  let zero_angle = || Expr::Value(Quantity::new(0.0, "deg"));
+  match function {
+    TransformFunction::Translate(..) => TransformFunction::Translate(zero(), zero()),
+    TransformFunction::Translate3d(..) => TransformFunction::Translate3d(zero(), zero(), zero()),
+    TransformFunction::Scale(..) => TransformFunction::Scale(1.0, 1.0),
+    TransformFunction::Scale3d(..) => TransformFunction::Scale3d(1.0, 1.0, 1.0),
+    TransformFunction::Rotate(_) => TransformFunction::Rotate(zero_angle()),
+    TransformFunction::Rotate3d(x, y, z, _) => {
+      TransformFunction::Rotate3d(*x, *y, *z, zero_angle())
+    }
+    TransformFunction::Skew(..) => TransformFunction::Skew(zero_angle(), zero_angle()),
+    TransformFunction::Matrix(_) => TransformFunction::Matrix([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]),
+    TransformFunction::Matrix3d(_) => TransformFunction::Matrix3d(Matrix::IDENTITY.0),
+    TransformFunction::Perspective(_) => TransformFunction::Perspective(None),
+  }
+}
+
+/// The 3D form of a 2D function, so that `translate()` blends with `translate3d()`
+fn to_3d(function: &TransformFunction) -> TransformFunction {
+  match function {
+    TransformFunction::Translate(x, y) => {
+      TransformFunction::Translate3d(x.clone(), y.clone(), Expr::Value(Quantity::number(0.0)))
+    }
+    TransformFunction::Scale(x, y) => TransformFunction::Scale3d(*x, *y, 1.0),
+    TransformFunction::Rotate(angle) => TransformFunction::Rotate3d(0.0, 0.0, 1.0, angle.clone()),
+    function => function.clone(),
+  }
+}
+
+/// Blend two functions of the same kind, `None` when they have to be blended as matrices
+fn transform_function(
+  from: &TransformFunction,
+  to: &TransformFunction,
+  progress: f64,
+) -> Option<TransformFunction> {
+  let number = |from: f64, to: f64| from + (to - from) * progress;
+  let same_kind = std::mem::discriminant(from) == std::mem::discriminant(to);
+  let (from, to) = if same_kind {
+    (from.clone(), to.clone())
+  } else {
+    (to_3d(from), to_3d(to))
+  };
+  Some(match (&from, &to) {
+    (TransformFunction::Translate(x1, y1), TransformFunction::Translate(x2, y2)) => {
+      TransformFunction::Translate(blend(x1, x2, progress)?, blend(y1, y2, progress)?)
+    }
+    (TransformFunction::Translate3d(x1, y1, z1), TransformFunction::Translate3d(x2, y2, z2)) => {
+      TransformFunction::Translate3d(
+        blend(x1, x2, progress)?,
+        blend(y1, y2, progress)?,
+        blend(z1, z2, progress)?,
+      )
+    }
+    (TransformFunction::Scale(x1, y1), TransformFunction::Scale(x2, y2)) => {
+      TransformFunction::Scale(number(*x1, *x2), number(*y1, *y2))
+    }
+    (TransformFunction::Scale3d(x1, y1, z1), TransformFunction::Scale3d(x2, y2, z2)) => {
+      TransformFunction::Scale3d(number(*x1, *x2), number(*y1, *y2), number(*z1, *z2))
+    }
+    (TransformFunction::Rotate(from), TransformFunction::Rotate(to)) => {
+      TransformFunction::Rotate(blend(from, to, progress)?)
+    }
+    // only rotations around the same axis blend angle by angle
+    (
+      TransformFunction::Rotate3d(x1, y1, z1, from),
+      TransformFunction::Rotate3d(x2, y2, z2, to),
+    ) if same_axis([*x1, *y1, *z1], [*x2, *y2, *z2]) => {
+      TransformFunction::Rotate3d(*x1, *y1, *z1, blend(from, to, progress)?)
+    }
+    (TransformFunction::Skew(x1, y1), TransformFunction::Skew(x2, y2)) => {
+      TransformFunction::Skew(blend(x1, x2, progress)?, blend(y1, y2, progress)?)
+    }
+    (TransformFunction::Perspective(Some(from)), TransformFunction::Perspective(Some(to))) => {
+      TransformFunction::Perspective(Some(blend(from, to, progress)?))
+    }
+    (TransformFunction::Perspective(None), TransformFunction::Perspective(None)) => {
+      TransformFunction::Perspective(None)
+    }
+    _ => return None,
+  })
+}
+
+fn same_axis(from: [f64; 3], to: [f64; 3]) -> bool {
+  let length = |[x, y, z]: [f64; 3]| (x * x + y * y + z * z).sqrt();
+  let (from_length, to_length) = (length(from), length(to));
+  from_length > 0.0
+    && to_length > 0.0
+    && (0..3).all(|index| (from[index] / from_length - to[index] / to_length).abs() < 1e-9)
+}
+
+/// Blend the rest of two lists as decomposed matrices, 2D ones when both are. `None` when
+/// a 3D matrix can't be decomposed, like a flattening `scaleZ(0)`.
+fn matrices(
+  from: &[TransformFunction],
+  to: &[TransformFunction],
+  progress: f64,
+) -> Option<TransformFunction> {
+  let from = TransformList(from.to_vec()).to_matrix()?;
+  let to = TransformList(to.to_vec()).to_matrix()?;
+  if from.is_2d() && to.is_2d() {
+    let blended = Decomposed::of(&from).interpolate(&Decomposed::of(&to), progress);
+    return Some(TransformFunction::Matrix(blended.recompose()));
+  }
+  let blended = Decomposed3d::of(&from)?.interpolate(&Decomposed3d::of(&to)?, progress);
+  Some(TransformFunction::Matrix3d(blended.recompose().0))
+}
+
+/// A 2D matrix as a translation, a rotation, a scale and the skew that remains
+///
+/// https://www.w3.org/TR/css-transforms-1/#decomposing-a-2d-matrix
+#[derive(Debug, Clone, Copy)]
+struct Decomposed {
+  translate: [f64; 2],
+  scale: [f64; 2],
+  /// In degrees
+  angle: f64,
+  /// The two columns of what is left once scale and rotation are taken out
+  remainder: [f64; 4],
+}
+
+impl Decomposed {
+  fn of(matrix: &Matrix) -> Decomposed {
+    let m = &matrix.0;
+    let [mut row0x, mut row0y, mut row1x, mut row1y] = [m[0], m[1], m[4], m[5]];
+    let mut scale = [row0x.hypot(row0y), row1x.hypot(row1y)];
+    // one axis was flipped
+    if row0x * row1y - row0y * row1x < 0.0 {
+      if row0x < row1y {
+        scale[0] = -scale[0];
+      } else {
+        scale[1] = -scale[1];
+      }
+    }
+    if scale[0] != 0.0 {
+      row0x /= scale[0];
+      row0y /= scale[0];
+    }
+    if scale[1] != 0.0 {
+      row1x /= scale[1];
+      row1y /= scale[1];
+    }
+    let angle = row0y.atan2(row0x);
+    if angle != 0.0 {
+      let (sn, cs) = (-row0y, row0x);
+      let [m11, m12, m21, m22] = [row0x, row0y, row1x, row1y];
+      row0x = cs * m11 + sn * m21;
+      row0y = cs * m12 + sn * m22;
+      row1x = -sn * m11 + cs * m21;
+      row1y = -sn * m12 + cs * m22;
+    }
+    Decomposed {
+      translate: [m[12], m[13]],
+      scale,
+      angle: angle.to_degrees(),
+      remainder: [row0x, row0y, row1x, row1y],
+    }
+  }
+
+  fn interpolate(&self, to: &Decomposed, progress: f64) -> Decomposed {
+    let (mut from, mut to) = (*self, *to);
+    // a flip of each axis is a rotation
+    if (from.scale[0] < 0.0 && to.scale[1] < 0.0) || (from.scale[1] < 0.0 && to.scale[0] < 0.0) {
+      from.scale = [-from.scale[0], -from.scale[1]];
+      from.angle += if from.angle < 0.0 { 180.0 } else { -180.0 };
+    }
+    // don't rotate the long way around
+    if from.angle == 0.0 {
+      from.angle = 360.0;
+    }
+    if to.angle == 0.0 {
+      to.angle = 360.0;
+    }
+    if (from.angle - to.angle).abs() > 180.0 {
+      if from.angle > to.angle {
+        from.angle -= 360.0;
+      } else {
+        to.angle -= 360.0;
+      }
+    }
+    let number = |from: f64, to: f64| from + (to - from) * progress;
+    Decomposed {
+      translate: [0, 1].map(|index| number(from.translate[index], to.translate[index])),
+      scale: [0, 1].map(|index| number(from.scale[index], to.scale[index])),
+      angle: number(from.angle, to.angle),
+      remainder: [0, 1, 2, 3].map(|index| number(from.remainder[index], to.remainder[index])),
+    }
+  }
+
+  /// The arguments of `matrix()`: the remainder, then the rotation, then the scale
+  fn recompose(&self) -> [f64; 6] {
+    let (sin, cos) = self.angle.to_radians().sin_cos();
+    let [m11, m12, m21, m22] = self.remainder;
+    let [scale_x, scale_y] = self.scale;
+    let values = [
+      (m11 * cos + m21 * sin) * scale_x,
+      (m12 * cos + m22 * sin) * scale_x,
+      (m21 * cos - m11 * sin) * scale_y,
+      (m22 * cos - m12 * sin) * scale_y,
+      self.translate[0],
+      self.translate[1],
+    ];
+    // the trigonometry leaves float noise like `1e-16` behind
+    values.map(|value| (value * 1e6).round() / 1e6)
+  }
+}
+
+/// A 3D matrix as a perspective, a translation, a rotation, a skew and a scale, applied in
+/// this order
+///
+/// https://www.w3.org/TR/css-transforms-2/#decomposing-a-3d-matrix
+#[derive(Debug, Clone, Copy)]
+struct Decomposed3d {
+  perspective: [f64; 4],
+  translate: [f64; 3],
+  /// The unit quaternion `x, y, z, w` of the rotation
+  quaternion: [f64; 4],
+  /// The XY, XZ and YZ shear factors
+  skew: [f64; 3],
+  scale: [f64; 3],
+}
+
+impl Decomposed3d {
+  /// `None` for a matrix that flattens everything, whose upper 3x3 part is singular
+  fn of(matrix: &Matrix) -> Option<Decomposed3d> {
+    if matrix.0[15] == 0.0 {
+      return None;
+    }
+    let m = matrix.0.map(|value| value / matrix.0[15]);
+    // the columns of the upper 3x3 part, the linear part of the transform
+    let mut columns = [0, 1, 2].map(|column| [0, 1, 2].map(|row| m[column * 4 + row]));
+    if dot(columns[0], cross(columns[1], columns[2])) == 0.0 {
+      return None;
+    }
+
+    // the bottom row is the perspective row times the rest of the matrix, solve for it
+    let perspective = if m[3] != 0.0 || m[7] != 0.0 || m[11] != 0.0 {
+      let mut transposed = [[0.0; 4]; 4];
+      for (row, values) in transposed.iter_mut().enumerate() {
+        *values = [m[row * 4], m[row * 4 + 1], m[row * 4 + 2], 0.0];
+      }
+      transposed[3][3] = 1.0;
+      solve(transposed, [m[3], m[7], m[11], m[15]])?
+    } else {
+      [0.0, 0.0, 0.0, 1.0]
+    };
+
+    // Gram-Schmidt leaves a rotation, with the scale and shear taken out
+    let mut scale = [0.0; 3];
+    let mut skew = [0.0; 3];
+    scale[0] = length(columns[0]);
+    columns[0] = scaled(columns[0], 1.0 / scale[0]);
+    skew[0] = dot(columns[0], columns[1]);
+    columns[1] = combine(columns[1], columns[0], -skew[0]);
+    scale[1] = length(columns[1]);
+    columns[1] = scaled(columns[1], 1.0 / scale[1]);
+    skew[0] /= scale[1];
+    skew[1] = dot(columns[0], columns[2]);
+    columns[2] = combine(columns[2], columns[0], -skew[1]);
+    skew[2] = dot(columns[1], columns[2]);
+    columns[2] = combine(columns[2], columns[1], -skew[2]);
+    scale[2] = length(columns[2]);
+    columns[2] = scaled(columns[2], 1.0 / scale[2]);
+    skew[1] /= scale[2];
+    skew[2] /= scale[2];
+    // a flipped coordinate system is a negative scale
+    if dot(columns[0], cross(columns[1], columns[2])) < 0.0 {
+      scale = scale.map(|scale| -scale);
+      columns = columns.map(|column| scaled(column, -1.0));
+    }
+
+    let at = |row: usize, column: usize| columns[column][row];
+    let part = |sign: [f64; 3]| {
+      0.5
+        * (1.0 + sign[0] * at(0, 0) + sign[1] * at(1, 1) + sign[2] * at(2, 2))
+          .max(0.0)
+          .sqrt()
+    };
+    let mut quaternion = [
+      part([1.0, -1.0, -1.0]),
+      part([-1.0, 1.0, -1.0]),
+      part([-1.0, -1.0, 1.0]),
+      part([1.0, 1.0, 1.0]),
+    ];
+    if at(1, 2) > at(2, 1) {
+      quaternion[0] = -quaternion[0];
+    }
+    if at(2, 0) > at(0, 2) {
+      quaternion[1] = -quaternion[1];
+    }
+    if at(0, 1) > at(1, 0) {
+      quaternion[2] = -quaternion[2];
+    }
+
+    Some(Decomposed3d {
+      perspective,
+      translate: [m[12], m[13], m[14]],
+      quaternion,
+      skew,
+      scale,
+    })
+  }
+
+  /// Blend the parts linearly, and the rotation along the shortest arc between the
+  /// quaternions
+  fn interpolate(&self, to: &Decomposed3d, progress: f64) -> Decomposed3d {
+    let number = |from: f64, to: f64| from + (to - from) * progress;
+    let product = (0..4)
+      .map(|index| self.quaternion[index] * to.quaternion[index])
+      .sum::<f64>()
+      .clamp(-1.0, 1.0);
+    let quaternion = if product.abs() == 1.0 {
+      self.quaternion
+    } else {
+      let theta = product.acos();
+      let w = (progress * theta).sin() / (1.0 - product * product).sqrt();
+      let from_weight = (progress * theta).cos() - product * w;
+      [0, 1, 2, 3].map(|index| self.quaternion[index] * from_weight + to.quaternion[index] * w)
+    };
+    Decomposed3d {
+      perspective: [0, 1, 2, 3].map(|index| number(self.perspective[index], to.perspective[index])),
+      translate: [0, 1, 2].map(|index| number(self.translate[index], to.translate[index])),
+      quaternion,
+      skew: [0, 1, 2].map(|index| number(self.skew[index], to.skew[index])),
+      scale: [0, 1, 2].map(|index| number(self.scale[index], to.scale[index])),
+    }
+  }
+
+  fn recompose(&self) -> Matrix {
+    let mut perspective = Matrix::IDENTITY;
+    for column in 0..4 {
+      perspective.0[column * 4 + 3] = self.perspective[column];
+    }
+    let mut translate = Matrix::IDENTITY;
+    translate.0[12..15].copy_from_slice(&self.translate);
+    let [x, y, z, w] = self.quaternion;
+    let rotate = Matrix([
+      1.0 - 2.0 * (y * y + z * z),
+      2.0 * (x * y + z * w),
+      2.0 * (x * z - y * w),
+      0.0,
+      2.0 * (x * y - z * w),
+      1.0 - 2.0 * (x * x + z * z),
+      2.0 * (y * z + x * w),
+      0.0,
+      2.0 * (x * z + y * w),
+      2.0 * (y * z - x * w),
+      1.0 - 2.0 * (x * x + y * y),
+      0.0,
+      0.0,
+      0.0,
+      0.0,
+      1.0,
+    ]);
+    let mut skew = Matrix::IDENTITY;
+    skew.0[4] = self.skew[0];
+    skew.0[8] = self.skew[1];
+    skew.0[9] = self.skew[2];
+    let mut scale = Matrix::IDENTITY;
+    for (index, factor) in self.scale.iter().enumerate() {
+      scale.0[index * 5] = *factor;
+    }
+    let matrix = perspective
+      .multiply(&translate)
+      .multiply(&rotate)
+      .multiply(&skew)
+      .multiply(&scale);
+    // the trigonometry leaves float noise like `1e-16` behind
+    Matrix(matrix.0.map(|value| (value * 1e6).round() / 1e6))
+  }
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+  a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+  [
+    a[1] * b[2] - a[2] * b[1],
+    a[2] * b[0] - a[0] * b[2],
+    a[0] * b[1] - a[1] * b[0],
+  ]
+}
+
+fn length(a: [f64; 3]) -> f64 {
+  dot(a, a).sqrt()
+}
+
+fn scaled(a: [f64; 3], factor: f64) -> [f64; 3] {
+  a.map(|value| value * factor)
+}
+
+/// `a + b * factor`
+fn combine(a: [f64; 3], b: [f64; 3], factor: f64) -> [f64; 3] {
+  [0, 1, 2].map(|index| a[index] + b[index] * factor)
+}
+
+/// Solve `matrix * x = rhs` by Gaussian elimination, `None` when the matrix is singular
+fn solve(mut matrix: [[f64; 4]; 4], mut rhs: [f64; 4]) -> Option<[f64; 4]> {
+  for column in 0..4 {
+    let pivot = (column..4).max_by(|a, b| {
+      matrix[*a][column]
+        .abs()
+        .total_cmp(&matrix[*b][column].abs())
+    })?;
+    if matrix[pivot][column] == 0.0 {
+      return None;
+    }
+    matrix.swap(column, pivot);
+    rhs.swap(column, pivot);
+    let pivot_row = matrix[column];
+    for row in column + 1..4 {
+      let factor = matrix[row][column] / pivot_row[column];
+      for (value, pivot) in matrix[row].iter_mut().zip(pivot_row).skip(column) {
+        *value -= factor * pivot;
+      }
+      rhs[row] -= factor * rhs[column];
+    }
+  }
+  let mut x = [0.0; 4];
+  for row in (0..4).rev() {
+    let rest = (row + 1..4)
+      .map(|index| matrix[row][index] * x[index])
+      .sum::<f64>();
+    x[row] = (rhs[row] - rest) / matrix[row][row];
+  }
+  Some(x)
+}
@@ -1,4 +1,22 @@
+pub mod calc;
+pub mod color;
+pub mod color_adjust;
+pub mod color_min;
+pub mod color_mix;
+pub mod color_space;
+pub mod contrast;
+pub mod gradient;
+pub mod gradient_legacy;
+pub mod incremental;
+pub mod interpolate;
+pub mod light_dark;
 pub mod node;
+pub mod number;
+pub mod parser;
+pub mod quantity;
 pub mod ref_ring;
+pub mod resolve;
+pub mod span;
 pub mod tokenizer;
+pub mod transform;
 pub mod unit;
@@ -0,0 +1,157 @@
+use crate::color::Color;
+use crate::node::{Function, Node};
+
+/// The color scheme a `light-dark()` value is resolved for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorScheme {
+  #[default]
+  Light,
+  Dark,
+}
+
+/// The two colors of `light-dark(<light>, <dark>)`
+///
+/// https://www.w3.org/TR/css-color-5/#light-dark
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LightDark<'n, 'a> {
+  pub light: &'n Node<'a>,
+  pub dark: &'n Node<'a>,
+}
+
+impl<'n, 'a> LightDark<'n, 'a> {
+  /// Split a `light-dark()` function, `None` for any other function or when it does not
+  /// have exactly two color arguments
+  pub fn parse(function: &'n Function<'a>) -> Option<Self> {
+    if !function.value.eq_ignore_ascii_case("light-dark") || function.unclosed {
+      return None;
+    }
+    let mut arguments = function
+      .nodes
+      .split(|node| matches!(node, Node::Div(div) if div.value == ","))
+      .map(|argument| match trim(argument) {
+        [color] if is_color(color) => Some(color),
+        _ => None,
+      });
+    let light = arguments.next()??;
+    let dark = arguments.next()??;
+    if arguments.next().is_some() {
+      return None;
+    }
+    Some(LightDark { light, dark })
+  }
+
+  /// The color used in `scheme`
+  pub fn get(&self, scheme: ColorScheme) -> &'n Node<'a> {
+    match scheme {
+      ColorScheme::Light => self.light,
+      ColorScheme::Dark => self.dark,
+    }
+  }
+}
+
+/// Replace every `light-dark()` in the tree, nested ones included, by its color for `scheme`
+pub fn resolve(nodes: &mut [Node], scheme: ColorScheme) {
+  for node in nodes.iter_mut() {
+    if let Node::Function(function) = node {
+      match LightDark::parse(function).map(|light_dark| light_dark.get(scheme).clone()) {
+        Some(color) => {
+          // the color may hold more `light-dark()`, look at it again
+          *node = color;
+          resolve(std::slice::from_mut(node), scheme);
+        }
+        None => resolve(&mut function.nodes, scheme),
+      }
+    }
+  }
+}
+
+/// Lower a value into its light and dark variants, for a fallback like
+/// `@media (prefers-color-scheme: dark)`. `None` when the value has no `light-dark()`.
+pub fn lower<'a>(nodes: &[Node<'a>]) -> Option<(Vec<Node<'a>>, Vec<Node<'a>>)> {
+  if !contains_light_dark(nodes) {
+    return None;
+  }
+  let [mut light, mut dark] = [nodes.to_vec(), nodes.to_vec()];
+  resolve(&mut light, ColorScheme::Light);
+  resolve(&mut dark, ColorScheme::Dark);
+  Some((light, dark))
+}
+
+/// Whether the tree has a `light-dark()` function
+pub fn contains_light_dark(nodes: &[Node]) -> bool {
+  nodes.iter().any(|node| match node {
+    Node::Function(function) => {
+      LightDark::parse(function).is_some() || contains_light_dark(&function.nodes)
+    }
+    _ => false,
+  })
+}
+
+/// Whether an argument can be a color: a color, a system color, a nested `light-dark()`, or
+/// a value only known once `var()` and `env()` are substituted
+fn is_color(node: &Node) -> bool {
+  match node {
+    Node::Word(word) => {
+      Color::from_node(node).is_some()
+        || SYSTEM_COLORS
+          .iter()
+          .any(|color| word.value.eq_ignore_ascii_case(color))
+    }
+    Node::Function(function) => {
+      Color::from_node(node).is_some()
+        || LightDark::parse(function).is_some()
+        || has_substitution(node)
+    }
+    Node::Placeholder(_) => true,
+    _ => false,
+  }
+}
+
+fn has_substitution(node: &Node) -> bool {
+  match node {
+    Node::Function(function) => {
+      ["var", "env"]
+        .iter()
+        .any(|name| function.value.eq_ignore_ascii_case(name))
+        || function.nodes.iter().any(has_substitution)
+    }
+    Node::Placeholder(_) => true,
+    _ => false,
+  }
+}
+
+/// https://www.w3.org/TR/css-color-4/#css-system-colors
+const SYSTEM_COLORS: [&str; 19] = [
+  "AccentColor",
+  "AccentColorText",
+  "ActiveText",
+  "ButtonBorder",
+  "ButtonFace",
+  "ButtonText",
+  "Canvas",
+  "CanvasText",
+  "Field",
+  "FieldText",
+  "GrayText",
+  "Highlight",
+  "HighlightText",
+  "LinkText",
+  "Mark",
+  "MarkText",
+  "SelectedItem",
+  "SelectedItemText",
+  "VisitedText",
+];
+
+fn trim<'n, 'a>(nodes: &'n [Node<'a>]) -> &'n [Node<'a>] {
+  let blank = |node: &Node| matches!(node, Node::Space(_) | Node::Comment(_));
+  let start = nodes
+    .iter()
+    .position(|node| !blank(node))
+    .unwrap_or(nodes.len());
+  let end = nodes
+    .iter()
+    .rposition(|node| !blank(node))
+    .map_or(start, |end| end + 1);
+  &nodes[start..end]
+}
@@ -1,41 +1,67 @@
+use crate::span::Span;
 use std::borrow::Cow;
+use std::fmt;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Word<'a> {
-  pub source_index: u32,
+  pub span: Span,
   pub value: Cow<'a, str>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Space<'a> {
-  pub source_index: u32,
+  pub span: Span,
   pub value: Cow<'a, str>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Comment<'a> {
-  pub source_index: u32,
+  pub span: Span,
+  /// The comment text without the `/*` `*/` or `//` delimiters
   pub value: Cow<'a, str>,
+  /// A `//` comment of the SCSS and Less dialects
+  pub inline: bool,
+  pub unclosed: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UnicodeRange<'a> {
-  pub source_index: u32,
+  pub span: Span,
   pub value: Cow<'a, str>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Div<'a> {
-  pub source_index: u32,
+  pub span: Span,
   pub value: Cow<'a, str>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct String<'a> {
-  pub source_index: u32,
+  pub span: Span,
+  /// The string content as written, without the quotes
   pub value: Cow<'a, str>,
+  pub quote: char,
+  pub unclosed: bool,
 }
 
+/// A substituted template expression, never split or rewritten
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholder<'a> {
+  pub span: Span,
+  pub value: Cow<'a, str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Function<'a> {
-  pub source_index: u32,
+  pub span: Span,
+  /// The function name, empty for a bare parenthesized group
   pub value: Cow<'a, str>,
   pub nodes: Vec<Node<'a>>,
+  pub unclosed: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Node<'a> {
   Word(Word<'a>),
   Space(Space<'a>),
@@ -44,6 +70,7 @@ pub enum Node<'a> {
   Div(Div<'a>),
   String(String<'a>),
   Function(Function<'a>),
+  Placeholder(Placeholder<'a>),
 }
 
 pub trait ClosableNode {
@@ -57,3 +84,181 @@ pub trait AdjacentAwareNode<'a> {
   /// The token at the end of the node
   fn after(&self) -> Cow<'a, str>;
 }
+
+impl<'a> ClosableNode for Comment<'a> {
+  fn unclosed(&self) -> bool {
+    self.unclosed
+  }
+}
+
+impl<'a> ClosableNode for String<'a> {
+  fn unclosed(&self) -> bool {
+    self.unclosed
+  }
+}
+
+impl<'a> ClosableNode for Function<'a> {
+  fn unclosed(&self) -> bool {
+    self.unclosed
+  }
+}
+
+impl<'a> Node<'a> {
+  pub fn span(&self) -> Span {
+    match self {
+      Node::Word(node) => node.span,
+      Node::Space(node) => node.span,
+      Node::Comment(node) => node.span,
+      Node::UnicodeRange(node) => node.span,
+      Node::Div(node) => node.span,
+      Node::String(node) => node.span,
+      Node::Function(node) => node.span,
+      Node::Placeholder(node) => node.span,
+    }
+  }
+
+  /// The `value` of the node: the text of words, the content of strings and comments,
+  /// the name of functions
+  pub fn value(&self) -> &str {
+    match self {
+      Node::Word(node) => &node.value,
+      Node::Space(node) => &node.value,
+      Node::Comment(node) => &node.value,
+      Node::UnicodeRange(node) => &node.value,
+      Node::Div(node) => &node.value,
+      Node::String(node) => &node.value,
+      Node::Function(node) => &node.value,
+      Node::Placeholder(node) => &node.value,
+    }
+  }
+
+  /// A word node not tied to any source
+  pub fn word(value: impl Into<Cow<'a, str>>) -> Node<'a> {
+    Node::Word(Word {
+      span: Span::default(),
+      value: value.into(),
+    })
+  }
+
+  /// A function node not tied to any source
+  pub fn function(name: impl Into<Cow<'a, str>>, nodes: Vec<Node<'a>>) -> Node<'a> {
+    Node::Function(Function {
+      span: Span::default(),
+      value: name.into(),
+      nodes,
+      unclosed: false,
+    })
+  }
+
+  /// Detach the node from the source it was parsed from
+  pub fn into_owned(self) -> Node<'static> {
+    fn owned(value: Cow<'_, str>) -> Cow<'static, str> {
+      Cow::Owned(value.into_owned())
+    }
+
+    match self {
+      Node::Word(Word { span, value }) => Node::Word(Word {
+        span,
+        value: owned(value),
+      }),
+      Node::Space(Space { span, value }) => Node::Space(Space {
+        span,
+        value: owned(value),
+      }),
+      Node::Comment(Comment {
+        span,
+        value,
+        inline,
+        unclosed,
+      }) => Node::Comment(Comment {
+        span,
+        value: owned(value),
+        inline,
+        unclosed,
+      }),
+      Node::UnicodeRange(UnicodeRange { span, value }) => Node::UnicodeRange(UnicodeRange {
+        span,
+        value: owned(value),
+      }),
+      Node::Div(Div { span, value }) => Node::Div(Div {
+        span,
+        value: owned(value),
+      }),
+      Node::String(String {
+        span,
+        value,
+        quote,
+        unclosed,
+      }) => Node::String(String {
+        span,
+        value: owned(value),
+        quote,
+        unclosed,
+      }),
+      Node::Function(Function {
+        span,
+        value,
+        nodes,
+        unclosed,
+      }) => Node::Function(Function {
+        span,
+        value: owned(value),
+        nodes: nodes.into_iter().map(Node::into_owned).collect(),
+        unclosed,
+      }),
+      Node::Placeholder(Placeholder { span, value }) => Node::Placeholder(Placeholder {
+        span,
+        value: owned(value),
+      }),
+    }
+  }
+}
+
+/// Serializes the node back to CSS text
+impl<'a> fmt::Display for Node<'a> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Node::Comment(node) if node.inline => write!(f, "//{}", node.value),
+      Node::Comment(node) => {
+        write!(f, "/*{}", node.value)?;
+        if !node.unclosed {
+          f.write_str("*/")?;
+        }
+        Ok(())
+      }
+      Node::String(node) => {
+        write!(f, "{}{}", node.quote, node.value)?;
+        if !node.unclosed {
+          write!(f, "{}", node.quote)?;
+        }
+        Ok(())
+      }
+      Node::Function(node) => {
+        write!(f, "{}(", node.value)?;
+        for child in &node.nodes {
+          write!(f, "{}", child)?;
+        }
+        if !node.unclosed {
+          f.write_str(")")?;
+        }
+        Ok(())
+      }
+      node => f.write_str(node.value()),
+    }
+  }
+}
+
+/// Whitespace and comments, which don't change what a value means
+pub(crate) fn is_blank(node: &Node) -> bool {
+  matches!(node, Node::Space(_) | Node::Comment(_))
+}
+
+/// The nodes that mean something, without whitespace and comments
+pub(crate) fn significant<'n, 'a>(nodes: &'n [Node<'a>]) -> Vec<&'n Node<'a>> {
+  nodes.iter().filter(|node| !is_blank(node)).collect()
+}
+
+/// Serialize a list of nodes back to CSS text
+pub fn stringify(nodes: &[Node]) -> std::string::String {
+  nodes.iter().map(|node| node.to_string()).collect()
+}
@@ -0,0 +1,104 @@
+use crate::unit::Dimension;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FormatOptions {
+  /// Round to at most this many decimal places, `None` keeps every significant digit
+  pub precision: Option<u32>,
+}
+
+/// Serialize a number to the shortest CSS text with the same value:
+/// no leading zero or plus sign, no trailing zeros, no negative zero,
+/// and an exponent where that is shorter (`1e6`, `1e-7`).
+pub fn format_number(value: f64, options: &FormatOptions) -> String {
+  if !value.is_finite() {
+    return format!("calc({})", non_finite_keyword(value));
+  }
+
+  let value = match options.precision {
+    Some(precision) => round_to(value, precision),
+    None => value,
+  };
+  if value == 0.0 {
+    // also drops the sign of negative zero
+    return "0".to_string();
+  }
+
+  // `{:e}` yields the shortest digits that round-trip, e.g. `1.2345e6`
+  let exponential = format!("{:e}", value.abs());
+  let (mantissa, exponent) = exponential.split_once('e').unwrap();
+  let exponent: i32 = exponent.parse().unwrap();
+  let digits = mantissa.replace('.', "");
+  let digits = digits.trim_end_matches('0');
+  let digits = if digits.is_empty() { "0" } else { digits };
+  let len = digits.len() as i32;
+
+  let plain = if exponent >= len - 1 {
+    format!("{}{}", digits, "0".repeat((exponent - len + 1) as usize))
+  } else if exponent >= 0 {
+    let (int, fraction) = digits.split_at(exponent as usize + 1);
+    format!("{}.{}", int, fraction)
+  } else {
+    format!(".{}{}", "0".repeat((-exponent - 1) as usize), digits)
+  };
+  let scientific = format!("{}e{}", digits, exponent - (len - 1));
+
+  let shortest = if scientific.len() < plain.len() {
+    scientific
+  } else {
+    plain
+  };
+  if value < 0.0 {
+    format!("-{}", shortest)
+  } else {
+    shortest
+  }
+}
+
+/// Serialize a dimension with its number in the shortest form, see [`format_number`].
+/// A unit that would read as part of the number is escaped, invalid and out of range
+/// numbers are kept as written.
+pub fn format_dimension(dimension: &Dimension, options: &FormatOptions) -> String {
+  let value = dimension.value();
+  if !value.is_finite() {
+    return format!("{}{}", dimension.number, dimension.unit);
+  }
+
+  let number = format_number(value, options);
+  let unit = dimension.unit.as_bytes();
+  let continues_number = matches!(
+    unit,
+    [b'0'..=b'9', ..]
+      | [b'.', b'0'..=b'9', ..]
+      | [b'e' | b'E', b'0'..=b'9', ..]
+      | [b'e' | b'E', b'+' | b'-', b'0'..=b'9', ..]
+  );
+  if continues_number {
+    // the first byte is ASCII, so slicing after it stays on a char boundary
+    format!("{}\\{:x} {}", number, unit[0], &dimension.unit[1..])
+  } else {
+    format!("{}{}", number, dimension.unit)
+  }
+}
+
+/// The math expression keyword for an infinite or NaN value: `infinity`, `-infinity`, `NaN`
+pub(crate) fn non_finite_keyword(value: f64) -> &'static str {
+  if value.is_nan() {
+    "NaN"
+  } else if value < 0.0 {
+    "-infinity"
+  } else {
+    "infinity"
+  }
+}
+
+#[inline]
+fn round_to(value: f64, precision: u32) -> f64 {
+  let factor = 10f64.powi(precision as i32);
+  let rounded = (value * factor).round() / factor;
+  // very large numbers have no fractional digits left to round
+  if rounded.is_finite() {
+    rounded
+  } else {
+    value
+  }
+}
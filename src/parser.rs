@@ -0,0 +1,183 @@
+use crate::node::{self, Node};
+use crate::span::Span;
+use crate::tokenizer::{Token, TokenType, Tokenizer, TokenizerOptions};
+
+/// Parse a CSS value into a tree of nodes
+pub fn parse(value: &str) -> Vec<Node<'_>> {
+  parse_with_options(value, Default::default())
+}
+
+pub fn parse_with_options<'a>(value: &'a str, options: TokenizerOptions<'_>) -> Vec<Node<'a>> {
+  let mut parser = Parser {
+    value,
+    tokenizer: Tokenizer::with_options(value, options),
+    peeked: None,
+  };
+  parser.parse_nodes(false).0
+}
+
+/// The node of a function like `rgb(0 0 0)` or a word serialized by the crate, built from
+/// the function name and its parsed arguments
+pub(crate) fn parse_serialized(value: &str) -> Node<'static> {
+  match value
+    .strip_suffix(')')
+    .and_then(|value| value.split_once('('))
+  {
+    Some((name, arguments)) => Node::function(
+      name.to_string(),
+      parse(arguments).into_iter().map(Node::into_owned).collect(),
+    ),
+    None => Node::word(value.to_string()),
+  }
+}
+
+struct Parser<'a, 'p> {
+  value: &'a str,
+  tokenizer: Tokenizer<'a, 'p>,
+  peeked: Option<Token<'a>>,
+}
+
+impl<'a> Parser<'a, '_> {
+  fn next(&mut self) -> Option<Token<'a>> {
+    if let Some(token) = self.peeked.take() {
+      return Some(token);
+    }
+    if self.tokenizer.end_of_file() {
+      None
+    } else {
+      Some(self.tokenizer.next_token())
+    }
+  }
+
+  fn peek(&mut self) -> Option<&Token<'a>> {
+    if self.peeked.is_none() {
+      self.peeked = self.next();
+    }
+    self.peeked.as_ref()
+  }
+
+  /// Parse nodes up to the end of input, or up to a closing parenthesis when `nested`.
+  /// Returns the nodes and the end offset of the closing parenthesis, if there was one.
+  fn parse_nodes(&mut self, nested: bool) -> (Vec<Node<'a>>, Option<usize>) {
+    let mut nodes = vec![];
+    while let Some(token) = self.next() {
+      let span = token.span;
+      let value = token.text.into();
+      let node = match token.kind {
+        TokenType::Space => Node::Space(node::Space { span, value }),
+        TokenType::Comment => Node::Comment(node::Comment {
+          span,
+          value,
+          inline: false,
+          unclosed: token.unclosed,
+        }),
+        TokenType::LineComment => Node::Comment(node::Comment {
+          span,
+          value,
+          inline: true,
+          unclosed: false,
+        }),
+        TokenType::String => string(token),
+        TokenType::Div => Node::Div(node::Div { span, value }),
+        TokenType::UnicodeRange => Node::UnicodeRange(node::UnicodeRange { span, value }),
+        TokenType::Placeholder => Node::Placeholder(node::Placeholder { span, value }),
+        TokenType::OpenParentheses => self.function("", span.start, span.end),
+        TokenType::CloseParentheses if nested => return (nodes, Some(span.end)),
+        TokenType::Word if self.peek().map(|it| it.kind) == Some(TokenType::OpenParentheses) => {
+          let open = self.next().unwrap();
+          self.function(token.text, span.start, open.span.end)
+        }
+        TokenType::Word | TokenType::CloseParentheses | TokenType::Unknown => {
+          Node::Word(node::Word { span, value })
+        }
+      };
+      nodes.push(node);
+    }
+    (nodes, None)
+  }
+
+  /// Parse the rest of a function, `open_end` is the offset right after its `(`
+  fn function(&mut self, name: &'a str, start: usize, open_end: usize) -> Node<'a> {
+    let (nodes, close) = if name.eq_ignore_ascii_case("url") {
+      self
+        .url_content(open_end)
+        .unwrap_or_else(|| self.parse_nodes(true))
+    } else {
+      self.parse_nodes(true)
+    };
+    Node::Function(node::Function {
+      span: Span::new(start, close.unwrap_or(self.value.len())),
+      value: name.into(),
+      nodes,
+      unclosed: close.is_none(),
+    })
+  }
+
+  /// An unquoted `url(` argument is taken as a single word up to the closing parenthesis
+  fn url_content(&mut self, open_end: usize) -> Option<(Vec<Node<'a>>, Option<usize>)> {
+    let rest = &self.value[open_end..];
+    let content_start = open_end + (rest.len() - rest.trim_start().len());
+    if matches!(
+      self.value.as_bytes().get(content_start),
+      Some(b'"' | b'\'') | None
+    ) {
+      return None;
+    }
+
+    let bytes = self.value.as_bytes();
+    let mut close = None;
+    let mut i = content_start;
+    while i < bytes.len() {
+      match bytes[i] {
+        b'\\' => i += 1,
+        b')' => {
+          close = Some(i);
+          break;
+        }
+        _ => {}
+      }
+      i += 1;
+    }
+    let content_end = close.unwrap_or(self.value.len());
+    let word = self.value[content_start..content_end].trim_end();
+    let word_end = content_start + word.len();
+
+    let mut nodes = vec![];
+    if content_start > open_end {
+      nodes.push(Node::Space(node::Space {
+        span: Span::new(open_end, content_start),
+        value: self.value[open_end..content_start].into(),
+      }));
+    }
+    nodes.push(Node::Word(node::Word {
+      span: Span::new(content_start, word_end),
+      value: word.into(),
+    }));
+    if content_end > word_end {
+      nodes.push(Node::Space(node::Space {
+        span: Span::new(word_end, content_end),
+        value: self.value[word_end..content_end].into(),
+      }));
+    }
+
+    let next = close.map_or(self.value.len(), |close| close + 1);
+    self.tokenizer.seek(next);
+    Some((nodes, close.map(|close| close + 1)))
+  }
+}
+
+fn string(token: Token) -> Node {
+  let text = token.text;
+  let quote = text.as_bytes()[0];
+  let value = if token.unclosed {
+    &text[1..]
+  } else {
+    &text[1..text.len() - 1]
+  };
+  Node::String(node::String {
+    span: token.span,
+    value: value.into(),
+    quote: quote as char,
+    unclosed: token.unclosed,
+  })
+}
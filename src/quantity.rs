@@ -0,0 +1,314 @@
+use crate::number::{format_number, non_finite_keyword, FormatOptions};
+use crate::unit::{convert_value, Dimension, UnitKind};
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A number with a unit, the computable counterpart of [`Dimension`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantity {
+  pub value: f64,
+  /// The unit as written, empty for a plain number
+  pub unit: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArithmeticError {
+  /// The operation is invalid whatever the context, e.g. `1px + 1s` or `1px * 1px`
+  IncompatibleUnits { left: String, right: String },
+  /// The units only become comparable at computed-value time, e.g. `50% + 10px` or `1em + 1px`.
+  /// A [`Sum`] keeps such terms side by side.
+  Unresolved { left: String, right: String },
+}
+
+impl fmt::Display for ArithmeticError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ArithmeticError::IncompatibleUnits { left, right } => {
+        write!(f, "incompatible units `{}` and `{}`", left, right)
+      }
+      ArithmeticError::Unresolved { left, right } => {
+        write!(
+          f,
+          "units `{}` and `{}` can not be resolved yet",
+          left, right
+        )
+      }
+    }
+  }
+}
+
+impl std::error::Error for ArithmeticError {}
+
+impl Quantity {
+  pub fn new(value: f64, unit: impl Into<String>) -> Quantity {
+    Quantity {
+      value,
+      unit: unit.into(),
+    }
+  }
+
+  pub fn number(value: f64) -> Quantity {
+    Quantity::new(value, "")
+  }
+
+  pub fn from_dimension(dimension: &Dimension) -> Quantity {
+    Quantity::new(dimension.value(), dimension.unit)
+  }
+
+  pub fn kind(&self) -> UnitKind {
+    UnitKind::of(&self.unit)
+  }
+
+  pub fn is_number(&self) -> bool {
+    self.unit.is_empty()
+  }
+
+  /// Convert to `unit`, `None` if the units are not compatible
+  pub fn to(&self, unit: &str) -> Option<Quantity> {
+    convert_value(self.value, &self.unit, unit).map(|value| Quantity::new(value, unit))
+  }
+
+  /// Convert `other` to the unit of `self`, or report why they can't be combined
+  fn align(&self, other: &Quantity) -> Result<f64, ArithmeticError> {
+    if let Some(other) = other.to(&self.unit) {
+      return Ok(other.value);
+    }
+    let (left, right) = (self.kind(), other.kind());
+    let unresolved = |kind: UnitKind| kind.is_length() || kind == UnitKind::Percentage;
+    let error = if left != UnitKind::Number
+      && right != UnitKind::Number
+      && left != UnitKind::Unknown
+      && right != UnitKind::Unknown
+      && (left == right
+        || left == UnitKind::Percentage
+        || right == UnitKind::Percentage
+        || (unresolved(left) && unresolved(right)))
+    {
+      ArithmeticError::Unresolved {
+        left: self.unit.clone(),
+        right: other.unit.clone(),
+      }
+    } else {
+      ArithmeticError::IncompatibleUnits {
+        left: self.unit.clone(),
+        right: other.unit.clone(),
+      }
+    };
+    Err(error)
+  }
+}
+
+impl fmt::Display for Quantity {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if !self.value.is_finite() && !self.is_number() {
+      return write!(f, "calc({}*1{})", non_finite_keyword(self.value), self.unit);
+    }
+    write!(
+      f,
+      "{}{}",
+      format_number(self.value, &FormatOptions::default()),
+      self.unit
+    )
+  }
+}
+
+impl Neg for Quantity {
+  type Output = Quantity;
+
+  fn neg(self) -> Quantity {
+    Quantity::new(-self.value, self.unit)
+  }
+}
+
+impl Add for Quantity {
+  type Output = Result<Quantity, ArithmeticError>;
+
+  fn add(self, rhs: Quantity) -> Self::Output {
+    let rhs = self.align(&rhs)?;
+    Ok(Quantity::new(self.value + rhs, self.unit))
+  }
+}
+
+impl Sub for Quantity {
+  type Output = Result<Quantity, ArithmeticError>;
+
+  fn sub(self, rhs: Quantity) -> Self::Output {
+    let rhs = self.align(&rhs)?;
+    Ok(Quantity::new(self.value - rhs, self.unit))
+  }
+}
+
+impl Mul for Quantity {
+  type Output = Result<Quantity, ArithmeticError>;
+
+  /// At least one side has to be a plain number
+  fn mul(self, rhs: Quantity) -> Self::Output {
+    if rhs.is_number() {
+      Ok(Quantity::new(self.value * rhs.value, self.unit))
+    } else if self.is_number() {
+      Ok(Quantity::new(self.value * rhs.value, rhs.unit))
+    } else {
+      Err(ArithmeticError::IncompatibleUnits {
+        left: self.unit,
+        right: rhs.unit,
+      })
+    }
+  }
+}
+
+impl Div for Quantity {
+  type Output = Result<Quantity, ArithmeticError>;
+
+  /// Divide by a plain number, or by a quantity of the same type giving a plain number.
+  /// Like in `calc()`, dividing by zero gives an infinite or NaN value rather than an error.
+  fn div(self, rhs: Quantity) -> Self::Output {
+    if rhs.is_number() {
+      return Ok(Quantity::new(self.value / rhs.value, self.unit));
+    }
+    if self.is_number() {
+      return Err(ArithmeticError::IncompatibleUnits {
+        left: self.unit,
+        right: rhs.unit,
+      });
+    }
+    let rhs = self.align(&rhs)?;
+    Ok(Quantity::number(self.value / rhs))
+  }
+}
+
+/// A sum of quantities that only resolves at computed-value time, like `50% + 10px`,
+/// with one term for each group of compatible units
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sum {
+  terms: Vec<Quantity>,
+}
+
+impl Sum {
+  pub fn terms(&self) -> &[Quantity] {
+    &self.terms
+  }
+
+  /// The quantity the sum comes down to, if all of its terms could be added up
+  pub fn as_quantity(&self) -> Option<&Quantity> {
+    match self.terms.as_slice() {
+      [quantity] => Some(quantity),
+      _ => None,
+    }
+  }
+
+  /// Add `term` to the first term it can be added to, or keep it as a term of its own
+  fn add_term(mut self, term: Quantity) -> Result<Sum, ArithmeticError> {
+    for existing in self.terms.iter_mut() {
+      match existing.clone() + term.clone() {
+        Ok(sum) => {
+          *existing = sum;
+          return Ok(self);
+        }
+        Err(ArithmeticError::Unresolved { .. }) => {}
+        Err(error) => return Err(error),
+      }
+    }
+    self.terms.push(term);
+    Ok(self)
+  }
+
+  /// Apply `operation` to every term
+  fn map(
+    self,
+    operation: impl Fn(Quantity) -> Result<Quantity, ArithmeticError>,
+  ) -> Result<Sum, ArithmeticError> {
+    let terms = self
+      .terms
+      .into_iter()
+      .map(operation)
+      .collect::<Result<_, _>>()?;
+    Ok(Sum { terms })
+  }
+}
+
+impl From<Quantity> for Sum {
+  fn from(quantity: Quantity) -> Sum {
+    Sum {
+      terms: vec![quantity],
+    }
+  }
+}
+
+impl fmt::Display for Sum {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let (first, rest) = match self.terms.split_first() {
+      Some((first, [])) => return first.fmt(f),
+      Some(terms) => terms,
+      None => return write!(f, "0"),
+    };
+    write!(f, "calc({}", first)?;
+    for term in rest {
+      if term.value < 0.0 {
+        write!(f, " - {}", -term.clone())?;
+      } else {
+        write!(f, " + {}", term)?;
+      }
+    }
+    write!(f, ")")
+  }
+}
+
+impl Neg for Sum {
+  type Output = Sum;
+
+  fn neg(self) -> Sum {
+    Sum {
+      terms: self.terms.into_iter().map(Neg::neg).collect(),
+    }
+  }
+}
+
+impl Add<Quantity> for Sum {
+  type Output = Result<Sum, ArithmeticError>;
+
+  fn add(self, rhs: Quantity) -> Self::Output {
+    self.add_term(rhs)
+  }
+}
+
+impl Add for Sum {
+  type Output = Result<Sum, ArithmeticError>;
+
+  fn add(self, rhs: Sum) -> Self::Output {
+    rhs.terms.into_iter().try_fold(self, Sum::add_term)
+  }
+}
+
+impl Sub<Quantity> for Sum {
+  type Output = Result<Sum, ArithmeticError>;
+
+  fn sub(self, rhs: Quantity) -> Self::Output {
+    self.add_term(-rhs)
+  }
+}
+
+impl Sub for Sum {
+  type Output = Result<Sum, ArithmeticError>;
+
+  fn sub(self, rhs: Sum) -> Self::Output {
+    self + -rhs
+  }
+}
+
+impl Mul<Quantity> for Sum {
+  type Output = Result<Sum, ArithmeticError>;
+
+  /// Multiply every term, at least one side of each product has to be a plain number
+  fn mul(self, rhs: Quantity) -> Self::Output {
+    self.map(|term| term * rhs.clone())
+  }
+}
+
+impl Div<Quantity> for Sum {
+  type Output = Result<Sum, ArithmeticError>;
+
+  /// Divide every term, a dimension has to cancel out of each of them
+  fn div(self, rhs: Quantity) -> Self::Output {
+    self.map(|term| term / rhs.clone())
+  }
+}
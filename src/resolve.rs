@@ -0,0 +1,145 @@
+use crate::calc::is_math_function;
+use crate::node::Node;
+use crate::number::{format_number, FormatOptions};
+use crate::unit::{convert_value, unit, Dimension, UnitKind};
+
+/// What relative lengths are relative to, every size is in `px`.
+/// A horizontal writing mode is assumed, so `vi` is `vw` and `cqb` is `cqh`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolutionContext {
+  pub root_font_size: f64,
+  pub font_size: f64,
+  /// The x-height of the font, `0.5em` when unknown
+  pub x_height: Option<f64>,
+  /// The cap height of the font, `0.7em` when unknown
+  pub cap_height: Option<f64>,
+  /// The advance of the `0` glyph, `0.5em` when unknown
+  pub ch_width: Option<f64>,
+  /// The computed line height, `1.2em` when unknown
+  pub line_height: Option<f64>,
+  pub viewport_width: Option<f64>,
+  pub viewport_height: Option<f64>,
+  /// The size of the query container, the viewport is used when there is none
+  pub container_width: Option<f64>,
+  pub container_height: Option<f64>,
+  /// What `100%` is, percentages are left alone when unknown
+  pub percentage_basis: Option<f64>,
+}
+
+impl Default for ResolutionContext {
+  /// The initial font size of browsers, nothing else known
+  fn default() -> Self {
+    ResolutionContext {
+      root_font_size: 16.0,
+      font_size: 16.0,
+      x_height: None,
+      cap_height: None,
+      ch_width: None,
+      line_height: None,
+      viewport_width: None,
+      viewport_height: None,
+      container_width: None,
+      container_height: None,
+      percentage_basis: None,
+    }
+  }
+}
+
+impl ResolutionContext {
+  /// The size of one `unit` in `px`, `None` if it is not a length or the context lacks
+  /// what it is relative to
+  pub fn unit_size(&self, unit: &str) -> Option<f64> {
+    let lower = unit.to_ascii_lowercase();
+    let viewport = |width: Option<f64>, height: Option<f64>, axis: &str| {
+      match axis {
+        "w" | "i" => width,
+        "h" | "b" => height,
+        "min" => Some(width?.min(height?)),
+        "max" => Some(width?.max(height?)),
+        _ => None,
+      }
+      .map(|size| size / 100.0)
+    };
+    match UnitKind::of(unit) {
+      UnitKind::AbsoluteLength => convert_value(1.0, unit, "px"),
+      UnitKind::FontRelativeLength => {
+        // the font metrics are only known for the element's own font
+        let (font_size, name, own) = match lower.strip_prefix('r') {
+          Some(name) => (self.root_font_size, name, false),
+          None => (self.font_size, lower.as_str(), true),
+        };
+        let metric =
+          |metric: Option<f64>, em: f64| metric.filter(|_| own).unwrap_or(em * font_size);
+        match name {
+          "em" => Some(font_size),
+          "ex" => Some(metric(self.x_height, 0.5)),
+          "cap" => Some(metric(self.cap_height, 0.7)),
+          "ch" => Some(metric(self.ch_width, 0.5)),
+          "ic" => Some(font_size),
+          "lh" => Some(metric(self.line_height, 1.2)),
+          _ => None,
+        }
+      }
+      UnitKind::ViewportLength => {
+        // the small, large and dynamic viewports are all the one viewport
+        let axis = lower
+          .trim_start_matches(['s', 'l', 'd'])
+          .trim_start_matches('v');
+        viewport(self.viewport_width, self.viewport_height, axis)
+      }
+      UnitKind::ContainerLength => viewport(
+        self.container_width.or(self.viewport_width),
+        self.container_height.or(self.viewport_height),
+        &lower[2..],
+      ),
+      UnitKind::Percentage => self.percentage_basis.map(|basis| basis / 100.0),
+      _ => None,
+    }
+  }
+
+  /// Resolve a length or percentage to `px`
+  pub fn resolve_value(&self, value: f64, unit: &str) -> Option<f64> {
+    self.unit_size(unit).map(|size| value * size)
+  }
+
+  /// Resolve a length or percentage dimension to `px`
+  pub fn resolve(&self, dimension: &Dimension) -> Option<f64> {
+    self.resolve_value(dimension.value(), dimension.unit)
+  }
+
+  /// Rewrite the relative lengths in the tree to `px`.
+  /// Percentages are only resolved outside of functions, or inside math functions,
+  /// as they mean something else in `rgb()` and the like.
+  pub fn resolve_nodes(&self, nodes: &mut [Node]) {
+    self.resolve_nodes_in(nodes, true);
+  }
+
+  fn resolve_nodes_in(&self, nodes: &mut [Node], percentages: bool) {
+    for node in nodes.iter_mut() {
+      match node {
+        Node::Word(word) => {
+          let resolved = match unit(&word.value) {
+            Some(dimension)
+              if dimension.kind().is_length()
+                || (percentages && dimension.kind() == UnitKind::Percentage) =>
+            {
+              self.resolve(&dimension)
+            }
+            _ => None,
+          };
+          if let Some(resolved) = resolved.filter(|resolved| resolved.is_finite()) {
+            let value = format_number(resolved, &FormatOptions::default());
+            word.value = format!("{}px", value).into();
+          }
+        }
+        Node::Function(function) if function.value.eq_ignore_ascii_case("url") => {}
+        Node::Function(function) => {
+          let percentages =
+            percentages && (function.value.is_empty() || is_math_function(&function.value));
+          self.resolve_nodes_in(&mut function.nodes, percentages);
+        }
+        _ => {}
+      }
+    }
+  }
+}
@@ -0,0 +1,54 @@
+use std::ops::Range;
+
+/// A byte range `start..end` into the source value
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub struct Span {
+  pub start: usize,
+  pub end: usize,
+}
+
+impl Span {
+  #[inline]
+  pub fn new(start: usize, end: usize) -> Span {
+    Span { start, end }
+  }
+
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.end.saturating_sub(self.start)
+  }
+
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Whether the byte offset falls inside the span, the end is exclusive
+  #[inline]
+  pub fn contains(&self, offset: usize) -> bool {
+    self.start <= offset && offset < self.end
+  }
+
+  /// The smallest span covering both spans
+  #[inline]
+  pub fn merge(&self, other: Span) -> Span {
+    Span {
+      start: self.start.min(other.start),
+      end: self.end.max(other.end),
+    }
+  }
+}
+
+impl From<Range<usize>> for Span {
+  #[inline]
+  fn from(range: Range<usize>) -> Self {
+    Span::new(range.start, range.end)
+  }
+}
+
+impl From<Span> for Range<usize> {
+  #[inline]
+  fn from(span: Span) -> Self {
+    span.start..span.end
+  }
+}
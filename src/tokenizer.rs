@@ -1,6 +1,7 @@
 // use crate::ref_ring::RefRing;
-use memchr::memchr;
+use crate::span::Span;
 use memchr::memmem::Finder;
+use memchr::{memchr, memchr2};
 use once_cell::sync::Lazy;
 use std::cell::RefCell;
 use std::clone::Clone;
@@ -18,7 +19,9 @@ pub enum TokenType {
   String,
   Div,
   Comment,
+  LineComment,
   UnicodeRange,
+  Placeholder,
   Unknown,
 }
 
@@ -32,41 +35,136 @@ impl std::fmt::Display for TokenType {
       TokenType::String => write!(f, "string"),
       TokenType::Div => write!(f, "div"),
       TokenType::Comment => write!(f, "comment"),
+      TokenType::LineComment => write!(f, "line-comment"),
       TokenType::UnicodeRange => write!(f, "unicode-range"),
+      TokenType::Placeholder => write!(f, "placeholder"),
       TokenType::Unknown => write!(f, "unknown"),
     }
   }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-/// quarter nary tuple (token_type, content, start_offset, end_offset), content is a slice with range `start_offset..end_offset`
-pub struct Token<'a>(pub TokenType, pub &'a str, pub usize, pub usize);
+pub struct Token<'a> {
+  pub kind: TokenType,
+  /// The token content, usually the slice of the source covered by `span`
+  pub text: &'a str,
+  pub span: Span,
+  /// Whether a string or comment runs to the end of input without its closing quote or `*/`
+  pub unclosed: bool,
+}
 
 impl<'a> Token<'a> {
-  pub fn new(kind: TokenType, content: &'a str, pos: usize, next: usize) -> Token {
-    Token(kind, content, pos, next)
+  pub fn new(kind: TokenType, text: &'a str, start: usize, end: usize) -> Token<'a> {
+    Token {
+      kind,
+      text,
+      span: Span::new(start, end),
+      unclosed: false,
+    }
+  }
+
+  #[inline]
+  pub fn with_unclosed(self, unclosed: bool) -> Token<'a> {
+    Token { unclosed, ..self }
   }
 }
 
+/// The stylesheet syntax the value was written in
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum Dialect {
+  #[default]
+  Css,
+  Scss,
+  Less,
+}
+
+impl Dialect {
+  /// Whether `//` starts a comment running to the end of the line
+  #[inline]
+  pub fn line_comments(self) -> bool {
+    matches!(self, Dialect::Scss | Dialect::Less)
+  }
+
+  /// The byte introducing an interpolation block, `#{...}` for SCSS and `@{...}` for Less
+  #[inline]
+  pub fn interpolation_sigil(self) -> Option<u8> {
+    match self {
+      Dialect::Css => None,
+      Dialect::Scss => Some(b'#'),
+      Dialect::Less => Some(b'@'),
+    }
+  }
+}
+
+/// A pattern for substituted template expressions, kept as a single opaque token
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PlaceholderPattern<'p> {
+  /// `${...}` expressions of a JS template literal, with balanced braces
+  TemplateExpression,
+  /// Anything from `prefix` up to and including the next `suffix`, e.g. `__PLACEHOLDER_3__`
+  Delimited { prefix: &'p str, suffix: &'p str },
+}
+
+impl PlaceholderPattern<'_> {
+  /// The end offset of the placeholder starting at `pos`, if there is one
+  pub fn match_at(&self, value: &str, pos: usize) -> Option<usize> {
+    let rest = &value.as_bytes()[pos..];
+    match *self {
+      PlaceholderPattern::TemplateExpression => {
+        if rest.starts_with(b"${") {
+          Some(index_of_interpolation_end(value, pos + 1))
+        } else {
+          None
+        }
+      }
+      PlaceholderPattern::Delimited { prefix, suffix } => {
+        if prefix.is_empty() || !rest.starts_with(prefix.as_bytes()) {
+          return None;
+        }
+        let from = pos + prefix.len();
+        memchr::memmem::find(&value.as_bytes()[from..], suffix.as_bytes())
+          .map(|v| v + from + suffix.len())
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub struct TokenizerOptions<'p> {
+  pub dialect: Dialect,
+  pub placeholder: Option<PlaceholderPattern<'p>>,
+}
+
 #[derive(Debug)]
-pub struct Tokenizer<'a> {
+pub struct Tokenizer<'a, 'p> {
   pub value: &'a str,
   length: usize,
   pos: RefCell<usize>,
+  options: TokenizerOptions<'p>,
   // buffer: RefCell<RefRing<'a>>,
 }
 
-impl<'a> Tokenizer<'a> {
-  pub fn new(value: &'a str) -> Tokenizer<'a> {
+impl<'a, 'p> Tokenizer<'a, 'p> {
+  pub fn new(value: &'a str) -> Tokenizer<'a, 'p> {
+    Tokenizer::with_options(value, Default::default())
+  }
+
+  pub fn with_options(value: &'a str, options: TokenizerOptions<'p>) -> Tokenizer<'a, 'p> {
     let length = value.len();
     Tokenizer {
       value,
       length,
       pos: RefCell::new(0),
+      options,
       // buffer: RefCell::new(Default::default()),
     }
   }
 
+  #[inline]
+  pub fn options(&self) -> TokenizerOptions<'p> {
+    self.options
+  }
+
   // #[inline]
   // fn push(&self, t: &'a str) {
   //   self.buffer.borrow_mut().push(t);
@@ -77,16 +175,68 @@ impl<'a> Tokenizer<'a> {
     *self.pos.borrow()
   }
 
+  /// Continue tokenizing from `pos`, which should be the start of a token
+  #[inline]
+  pub fn seek(&self, pos: usize) {
+    self.pos.replace(pos);
+  }
+
   pub fn end_of_file(&self) -> bool {
     self.position() >= self.length
   }
 
+  #[inline]
+  fn placeholder_end(&self, pos: usize) -> Option<usize> {
+    match self.options.placeholder {
+      Some(pattern) if pos < self.length => pattern.match_at(self.value, pos),
+      _ => None,
+    }
+  }
+
+  /// A word ends at a delimiter or where a placeholder begins
+  #[inline]
+  fn index_of_word_end(&self, start: usize) -> usize {
+    let bytes = self.value.as_bytes();
+    let interpolation = self.options.dialect.interpolation_sigil();
+    let mut i = start;
+    let len = bytes.len();
+
+    while i < len {
+      match bytes[i] {
+        b if Some(b) == interpolation && bytes.get(i + 1) == Some(&b'{') => {
+          i = index_of_interpolation_end(self.value, i + 1);
+        }
+        0..=32 | b'\'' | b'"' | b',' | b':' | b'/' | b'*' | b'(' | b')' => {
+          return i;
+        }
+        _ if i > start && self.placeholder_end(i).is_some() => {
+          return i;
+        }
+        b'\\' => i += 2,
+        _ => i += 1,
+      };
+    }
+    // an escape can't reach past the input
+    i.min(len)
+  }
+
   #[inline]
   fn pos_plus_one(&self) {
     self.pos.replace_with(|it| *it + 1);
   }
 
   pub fn next_token(&self) -> Token<'a> {
+    if let Some(next) = self.placeholder_end(self.position()) {
+      let start = self.position();
+      self.pos.replace(next);
+      return Token::new(
+        TokenType::Placeholder,
+        sub_str(self.value, start, next),
+        start,
+        next,
+      );
+    }
+
     let mut code = char_code_at(self.value, self.position());
 
     let current_token: Token;
@@ -102,7 +252,7 @@ impl<'a> Tokenizer<'a> {
           }
         }
 
-        current_token = Token(
+        current_token = Token::new(
           TokenType::Space,
           self.value[self.position()..next].into(),
           self.position(),
@@ -113,6 +263,7 @@ impl<'a> Tokenizer<'a> {
       }
       quote @ (b'\'' | b'"') => {
         let mut next = self.position();
+        let mut unclosed = false;
         loop {
           let mut escaped = false;
           next = match index_of_byte(self.value, quote, next + 1) {
@@ -124,7 +275,10 @@ impl<'a> Tokenizer<'a> {
               }
               next
             }
-            None => self.length - 1,
+            None => {
+              unclosed = true;
+              self.length - 1
+            }
           };
 
           if !escaped {
@@ -132,31 +286,50 @@ impl<'a> Tokenizer<'a> {
           }
         }
 
-        current_token = Token(
+        current_token = Token::new(
           TokenType::String,
           sub_str(self.value, self.position(), next + 1),
           self.position(),
           next + 1,
-        );
+        )
+        .with_unclosed(unclosed);
         self.pos.replace(next + 1);
       }
       b'/' if char_code_at(self.value, self.position() + 1) == b'*' => {
-        let next = index_of_end_comment(self.value, self.position()).unwrap_or(self.length);
-        current_token = Token(
+        let close = index_of_end_comment(self.value, self.position() + 2);
+        let next = close.unwrap_or(self.length);
+        let end = (next + 2).min(self.length);
+        current_token = Token::new(
           TokenType::Comment,
           sub_str(self.value, self.position() + 2, next),
           self.position(),
-          next + 1,
+          end,
+        )
+        .with_unclosed(close.is_none());
+        self.pos.replace(end);
+      }
+      // `http://` is a url, not the start of a comment
+      b'/'
+        if self.options.dialect.line_comments()
+          && char_code_at(self.value, self.position() + 1) == b'/'
+          && (self.position() == 0 || char_code_at(self.value, self.position() - 1) != b':') =>
+      {
+        let next = index_of_line_end(self.value, self.position());
+        current_token = Token::new(
+          TokenType::LineComment,
+          sub_str(self.value, self.position() + 2, next),
+          self.position(),
+          next,
         );
-        self.pos.replace(next + 2);
+        self.pos.replace(next);
       }
       b'/' | b',' | b':' | b'(' | b')' => {
         let start = self.position();
-        current_token = Token(get_token_type(code), get_str(code), start, start + 1);
+        current_token = Token::new(get_token_type(code), get_str(code), start, start + 1);
         self.pos_plus_one();
       }
       _ => {
-        let mut next = index_of_word_end(self.value, self.position());
+        let mut next = self.index_of_word_end(self.position());
         if next == self.position() {
           next += 1;
         }
@@ -183,21 +356,40 @@ fn index_of_end_comment(value: &str, from_index: usize) -> Option<usize> {
 }
 
 #[inline]
-fn index_of_word_end(s: &str, start: usize) -> usize {
+fn index_of_line_end(value: &str, from_index: usize) -> usize {
+  let bytes = &value.as_bytes()[from_index..];
+  memchr2(b'\n', b'\r', bytes)
+    .map(|v| v + from_index)
+    .unwrap_or(value.len())
+}
+
+/// Find the end of an interpolation block, `start` points at the opening `{`.
+/// Nested braces and quoted strings are skipped, an unclosed block runs to the end of input.
+fn index_of_interpolation_end(s: &str, start: usize) -> usize {
   let bytes = s.as_bytes();
-  let mut i = start;
   let len = bytes.len();
+  let mut depth = 0usize;
+  let mut quote = None;
+  let mut i = start;
 
   while i < len {
-    match bytes[i] {
-      0..=32 | b'\'' | b'"' | b',' | b':' | b'/' | b'*' | b'(' | b')' => {
-        return i;
+    match (quote, bytes[i]) {
+      (_, b'\\') => i += 1,
+      (Some(q), b) if b == q => quote = None,
+      (Some(_), _) => {}
+      (None, b @ (b'\'' | b'"' | b'`')) => quote = Some(b),
+      (None, b'{') => depth += 1,
+      (None, b'}') => {
+        depth -= 1;
+        if depth == 0 {
+          return i + 1;
+        }
       }
-      b'\\' => i += 2,
-      _ => i += 1,
-    };
+      _ => {}
+    }
+    i += 1;
   }
-  i
+  len
 }
 
 #[inline]
@@ -0,0 +1,501 @@
+use crate::calc::{serialize, value, Expr};
+use crate::node::{significant, Function, Node};
+use crate::number::{format_number, FormatOptions};
+use crate::quantity::Quantity;
+use crate::unit::UnitKind;
+use std::fmt;
+
+/// A transform function, the single axis forms like `translateX()` are folded into the
+/// general one of their dimension
+///
+/// https://www.w3.org/TR/css-transforms-2/#transform-functions
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransformFunction {
+  /// `translate()`, `translateX()` and `translateY()`
+  Translate(Expr, Expr),
+  /// `translate3d()` and `translateZ()`
+  Translate3d(Expr, Expr, Expr),
+  /// `scale()`, `scaleX()` and `scaleY()`, percentages are converted to numbers
+  Scale(f64, f64),
+  /// `scale3d()` and `scaleZ()`
+  Scale3d(f64, f64, f64),
+  /// `rotate()` and `rotateZ()`
+  Rotate(Expr),
+  /// `rotate3d()`, `rotateX()` and `rotateY()`, an axis and an angle
+  Rotate3d(f64, f64, f64, Expr),
+  /// `skew()`, `skewX()` and `skewY()`
+  Skew(Expr, Expr),
+  Matrix([f64; 6]),
+  Matrix3d([f64; 16]),
+  /// The distance of `perspective()`, `None` for `none`
+  Perspective(Option<Expr>),
+}
+
+/// The functions of a `transform` value, empty for `none`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TransformList(pub Vec<TransformFunction>);
+
+/// A 4x4 transformation matrix in column-major order, the order of the `matrix3d()`
+/// arguments
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix(pub [f64; 16]);
+
+impl TransformList {
+  /// Parse a `transform` value, `None` if it is not a valid one
+  pub fn parse(nodes: &[Node]) -> Option<TransformList> {
+    let nodes = significant(nodes);
+    match nodes.as_slice() {
+      [Node::Word(word)] if word.value.eq_ignore_ascii_case("none") => {
+        Some(TransformList::default())
+      }
+      [] => None,
+      nodes => nodes
+        .iter()
+        .map(|node| match node {
+          Node::Function(function) => TransformFunction::parse(function),
+          _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(TransformList),
+    }
+  }
+
+  /// The matrix of the whole list, `None` if a length is relative, like a percentage or
+  /// `em`, or a value is not computable
+  pub fn to_matrix(&self) -> Option<Matrix> {
+    self
+      .0
+      .iter()
+      .try_fold(Matrix::IDENTITY, |matrix, function| {
+        Some(matrix.multiply(&function.to_matrix()?))
+      })
+  }
+}
+
+impl TransformFunction {
+  /// Parse a transform function, `None` for any other function or invalid arguments
+  pub fn parse(function: &Function) -> Option<TransformFunction> {
+    if function.unclosed {
+      return None;
+    }
+    let name = function.value.to_ascii_lowercase();
+    if name == "perspective" && is_none(function) {
+      return Some(TransformFunction::Perspective(None));
+    }
+    let arguments = function
+      .nodes
+      .split(|node| matches!(node, Node::Div(div) if div.value == ","))
+      .map(|argument| match significant(argument).as_slice() {
+        [node] => value(node),
+        _ => None,
+      })
+      .collect::<Option<Vec<_>>>()?;
+    let zero = || Expr::Value(Quantity::number(0.0));
+    Some(match (name.as_str(), arguments.as_slice()) {
+      ("translate", [x]) if length_percentage(x) => TransformFunction::Translate(x.clone(), zero()),
+      ("translate", [x, y]) if length_percentage(x) && length_percentage(y) => {
+        TransformFunction::Translate(x.clone(), y.clone())
+      }
+      ("translatex", [x]) if length_percentage(x) => {
+        TransformFunction::Translate(x.clone(), zero())
+      }
+      ("translatey", [y]) if length_percentage(y) => {
+        TransformFunction::Translate(zero(), y.clone())
+      }
+      ("translatez", [z]) if length(z) => TransformFunction::Translate3d(zero(), zero(), z.clone()),
+      ("translate3d", [x, y, z]) if length_percentage(x) && length_percentage(y) && length(z) => {
+        TransformFunction::Translate3d(x.clone(), y.clone(), z.clone())
+      }
+      ("scale", [x]) => {
+        let x = scale(x)?;
+        TransformFunction::Scale(x, x)
+      }
+      ("scale", [x, y]) => TransformFunction::Scale(scale(x)?, scale(y)?),
+      ("scalex", [x]) => TransformFunction::Scale(scale(x)?, 1.0),
+      ("scaley", [y]) => TransformFunction::Scale(1.0, scale(y)?),
+      ("scalez", [z]) => TransformFunction::Scale3d(1.0, 1.0, scale(z)?),
+      ("scale3d", [x, y, z]) => TransformFunction::Scale3d(scale(x)?, scale(y)?, scale(z)?),
+      ("rotate" | "rotatez", [angle]) if is_angle(angle) => {
+        TransformFunction::Rotate(angle.clone())
+      }
+      ("rotatex", [angle]) if is_angle(angle) => {
+        TransformFunction::Rotate3d(1.0, 0.0, 0.0, angle.clone())
+      }
+      ("rotatey", [angle]) if is_angle(angle) => {
+        TransformFunction::Rotate3d(0.0, 1.0, 0.0, angle.clone())
+      }
+      ("rotate3d", [x, y, z, angle]) if is_angle(angle) => {
+        TransformFunction::Rotate3d(number(x)?, number(y)?, number(z)?, angle.clone())
+      }
+      ("skew", [x]) if is_angle(x) => TransformFunction::Skew(x.clone(), zero()),
+      ("skew", [x, y]) if is_angle(x) && is_angle(y) => {
+        TransformFunction::Skew(x.clone(), y.clone())
+      }
+      ("skewx", [x]) if is_angle(x) => TransformFunction::Skew(x.clone(), zero()),
+      ("skewy", [y]) if is_angle(y) => TransformFunction::Skew(zero(), y.clone()),
+      ("matrix", values) if values.len() == 6 => {
+        let mut matrix = [0.0; 6];
+        for (value, argument) in matrix.iter_mut().zip(values) {
+          *value = number(argument)?;
+        }
+        TransformFunction::Matrix(matrix)
+      }
+      ("matrix3d", values) if values.len() == 16 => {
+        let mut matrix = [0.0; 16];
+        for (value, argument) in matrix.iter_mut().zip(values) {
+          *value = number(argument)?;
+        }
+        TransformFunction::Matrix3d(matrix)
+      }
+      ("perspective", [distance]) if length(distance) && !is_negative(distance) => {
+        TransformFunction::Perspective(Some(distance.clone()))
+      }
+      _ => return None,
+    })
+  }
+
+  /// Whether the function leaves everything in place. Only the 2D functions are considered,
+  /// the 3D ones are often there to put the element on a layer of its own.
+  pub fn is_identity(&self) -> bool {
+    match self {
+      TransformFunction::Translate(x, y) | TransformFunction::Skew(x, y) => {
+        is_zero(x) && is_zero(y)
+      }
+      TransformFunction::Scale(x, y) => *x == 1.0 && *y == 1.0,
+      TransformFunction::Rotate(angle) => is_zero(angle),
+      TransformFunction::Matrix(matrix) => *matrix == [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+      _ => false,
+    }
+  }
+
+  /// The matrix of the function, `None` if a length is relative or a value not computable
+  pub fn to_matrix(&self) -> Option<Matrix> {
+    let mut matrix = Matrix::IDENTITY;
+    let m = &mut matrix.0;
+    match self {
+      TransformFunction::Translate(x, y) => {
+        m[12] = pixels(x)?;
+        m[13] = pixels(y)?;
+      }
+      TransformFunction::Translate3d(x, y, z) => {
+        m[12] = pixels(x)?;
+        m[13] = pixels(y)?;
+        m[14] = pixels(z)?;
+      }
+      TransformFunction::Scale(x, y) => {
+        m[0] = *x;
+        m[5] = *y;
+      }
+      TransformFunction::Scale3d(x, y, z) => {
+        m[0] = *x;
+        m[5] = *y;
+        m[10] = *z;
+      }
+      TransformFunction::Rotate(angle) => return Some(rotation(0.0, 0.0, 1.0, radians(angle)?)),
+      TransformFunction::Rotate3d(x, y, z, angle) => {
+        return Some(rotation(*x, *y, *z, radians(angle)?))
+      }
+      TransformFunction::Skew(x, y) => {
+        m[4] = radians(x)?.tan();
+        m[1] = radians(y)?.tan();
+      }
+      TransformFunction::Matrix([a, b, c, d, e, f]) => {
+        m[0] = *a;
+        m[1] = *b;
+        m[4] = *c;
+        m[5] = *d;
+        m[12] = *e;
+        m[13] = *f;
+      }
+      TransformFunction::Matrix3d(values) => *m = *values,
+      // distances below 1px are clamped to it
+      TransformFunction::Perspective(Some(distance)) => m[11] = -1.0 / pixels(distance)?.max(1.0),
+      TransformFunction::Perspective(None) => {}
+    }
+    Some(matrix)
+  }
+}
+
+impl Matrix {
+  pub const IDENTITY: Matrix = Matrix([
+    1.0, 0.0, 0.0, 0.0, //
+    0.0, 1.0, 0.0, 0.0, //
+    0.0, 0.0, 1.0, 0.0, //
+    0.0, 0.0, 0.0, 1.0,
+  ]);
+
+  /// `self` followed by `other`, as in a transform list where `other` comes after `self`
+  pub fn multiply(&self, other: &Matrix) -> Matrix {
+    let mut result = [0.0; 16];
+    for column in 0..4 {
+      for row in 0..4 {
+        result[column * 4 + row] = (0..4)
+          .map(|index| self.0[index * 4 + row] * other.0[column * 4 + index])
+          .sum();
+      }
+    }
+    Matrix(result)
+  }
+
+  /// Whether the matrix can be written as `matrix()`
+  pub fn is_2d(&self) -> bool {
+    let m = &self.0;
+    [m[2], m[3], m[6], m[7], m[8], m[9], m[11], m[14]]
+      .iter()
+      .all(|value| *value == 0.0)
+      && m[10] == 1.0
+      && m[15] == 1.0
+  }
+}
+
+impl fmt::Display for Matrix {
+  /// `matrix()` when possible, `matrix3d()` otherwise, with the values rounded to 6 places
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let options = FormatOptions { precision: Some(6) };
+    let values = if self.is_2d() {
+      let m = &self.0;
+      vec![m[0], m[1], m[4], m[5], m[12], m[13]]
+    } else {
+      self.0.to_vec()
+    };
+    let values = values
+      .into_iter()
+      .map(|value| format_number(value, &options))
+      .collect::<Vec<_>>();
+    let name = if self.is_2d() { "matrix" } else { "matrix3d" };
+    write!(f, "{}({})", name, values.join(", "))
+  }
+}
+
+impl fmt::Display for TransformFunction {
+  /// The shortest form of the function, like `translate(10px)` for `translate(10px, 0)`
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      TransformFunction::Translate(x, y) if is_zero(y) => write!(f, "translate({})", Value(x)),
+      TransformFunction::Translate(x, y) if is_zero(x) => write!(f, "translateY({})", Value(y)),
+      TransformFunction::Translate(x, y) => write!(f, "translate({}, {})", Value(x), Value(y)),
+      TransformFunction::Translate3d(x, y, z) if is_zero(x) && is_zero(y) => {
+        write!(f, "translateZ({})", Value(z))
+      }
+      TransformFunction::Translate3d(x, y, z) => {
+        write!(f, "translate3d({}, {}, {})", Value(x), Value(y), Value(z))
+      }
+      TransformFunction::Scale(x, y) if x == y => write!(f, "scale({})", Number(*x)),
+      TransformFunction::Scale(x, y) if *y == 1.0 => write!(f, "scaleX({})", Number(*x)),
+      TransformFunction::Scale(x, y) if *x == 1.0 => write!(f, "scaleY({})", Number(*y)),
+      TransformFunction::Scale(x, y) => write!(f, "scale({}, {})", Number(*x), Number(*y)),
+      TransformFunction::Scale3d(x, y, z) if *x == 1.0 && *y == 1.0 => {
+        write!(f, "scaleZ({})", Number(*z))
+      }
+      TransformFunction::Scale3d(x, y, z) => {
+        write!(f, "scale3d({}, {}, {})", Number(*x), Number(*y), Number(*z))
+      }
+      TransformFunction::Rotate(angle) => write!(f, "rotate({})", Value(angle)),
+      TransformFunction::Rotate3d(x, y, z, angle) => match normalize(*x, *y, *z) {
+        Some([x, y, z]) if y == 0.0 && z == 0.0 && x > 0.0 => {
+          write!(f, "rotateX({})", Value(angle))
+        }
+        Some([x, y, z]) if x == 0.0 && z == 0.0 && y > 0.0 => {
+          write!(f, "rotateY({})", Value(angle))
+        }
+        // `rotateZ()`, which is `rotate()`
+        Some([x, y, z]) if x == 0.0 && y == 0.0 && z > 0.0 => {
+          write!(f, "rotate({})", Value(angle))
+        }
+        _ => write!(
+          f,
+          "rotate3d({}, {}, {}, {})",
+          Number(*x),
+          Number(*y),
+          Number(*z),
+          Value(angle)
+        ),
+      },
+      TransformFunction::Skew(x, y) if is_zero(y) => write!(f, "skew({})", Value(x)),
+      TransformFunction::Skew(x, y) if is_zero(x) => write!(f, "skewY({})", Value(y)),
+      TransformFunction::Skew(x, y) => write!(f, "skew({}, {})", Value(x), Value(y)),
+      TransformFunction::Matrix(values) => write_numbers(f, "matrix", values),
+      TransformFunction::Matrix3d(values) => {
+        let matrix = Matrix(*values);
+        if matrix.is_2d() {
+          write_numbers(
+            f,
+            "matrix",
+            &[
+              values[0], values[1], values[4], values[5], values[12], values[13],
+            ],
+          )
+        } else {
+          write_numbers(f, "matrix3d", values)
+        }
+      }
+      TransformFunction::Perspective(Some(distance)) => {
+        write!(f, "perspective({})", Value(distance))
+      }
+      TransformFunction::Perspective(None) => f.write_str("perspective(none)"),
+    }
+  }
+}
+
+impl fmt::Display for TransformList {
+  /// The shortest equivalent list: the functions in their shortest form, without the
+  /// identity ones. One is kept if they all are, as `none` would not create a stacking
+  /// context and containing block like any other transform does.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut functions = self
+      .0
+      .iter()
+      .filter(|function| !function.is_identity())
+      .peekable();
+    if functions.peek().is_none() {
+      return match self.0.first() {
+        Some(function) => function.fmt(f),
+        None => f.write_str("none"),
+      };
+    }
+    for (index, function) in functions.enumerate() {
+      if index > 0 {
+        f.write_str(" ")?;
+      }
+      function.fmt(f)?;
+    }
+    Ok(())
+  }
+}
+
+/// A length, percentage or angle, with zero written as `0`
+struct Value<'e>(&'e Expr);
+
+impl fmt::Display for Value<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.0 {
+      expr if is_zero(expr) => f.write_str("0"),
+      expr => f.write_str(&serialize(expr)),
+    }
+  }
+}
+
+struct Number(f64);
+
+impl fmt::Display for Number {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&format_number(self.0, &FormatOptions::default()))
+  }
+}
+
+fn write_numbers(f: &mut fmt::Formatter<'_>, name: &str, values: &[f64]) -> fmt::Result {
+  let values = values
+    .iter()
+    .map(|value| Number(*value).to_string())
+    .collect::<Vec<_>>();
+  write!(f, "{}({})", name, values.join(", "))
+}
+
+fn is_none(function: &Function) -> bool {
+  matches!(
+    significant(&function.nodes).as_slice(),
+    [Node::Word(word)] if word.value.eq_ignore_ascii_case("none")
+  )
+}
+
+fn is_zero(expr: &Expr) -> bool {
+  matches!(expr, Expr::Value(quantity) if quantity.value == 0.0)
+}
+
+fn is_negative(expr: &Expr) -> bool {
+  matches!(expr, Expr::Value(quantity) if quantity.value < 0.0)
+}
+
+/// Whether a value may be of the type, calculations that are not resolved yet may be
+fn has_kind(expr: &Expr, accepts: impl Fn(UnitKind) -> bool) -> bool {
+  match expr {
+    Expr::Value(quantity) if quantity.is_number() => quantity.value == 0.0,
+    Expr::Value(quantity) => accepts(quantity.kind()),
+    _ => true,
+  }
+}
+
+fn length(expr: &Expr) -> bool {
+  has_kind(expr, UnitKind::is_length)
+}
+
+fn length_percentage(expr: &Expr) -> bool {
+  has_kind(expr, |kind| {
+    kind.is_length() || kind == UnitKind::Percentage
+  })
+}
+
+fn is_angle(expr: &Expr) -> bool {
+  has_kind(expr, |kind| kind == UnitKind::Angle)
+}
+
+fn number(expr: &Expr) -> Option<f64> {
+  match expr {
+    Expr::Value(quantity) if quantity.is_number() => Some(quantity.value),
+    _ => None,
+  }
+}
+
+/// A scale factor, a number or a percentage
+fn scale(expr: &Expr) -> Option<f64> {
+  match expr {
+    Expr::Value(quantity) if quantity.kind() == UnitKind::Percentage => {
+      Some(quantity.value / 100.0)
+    }
+    expr => number(expr),
+  }
+}
+
+fn pixels(expr: &Expr) -> Option<f64> {
+  match expr {
+    Expr::Value(quantity) if quantity.is_number() => Some(quantity.value),
+    Expr::Value(quantity) => quantity.to("px").map(|quantity| quantity.value),
+    _ => None,
+  }
+}
+
+fn radians(expr: &Expr) -> Option<f64> {
+  match expr {
+    Expr::Value(quantity) if quantity.is_number() => Some(quantity.value),
+    Expr::Value(quantity) => quantity
+      .to("deg")
+      .map(|quantity| quantity.value.to_radians()),
+    _ => None,
+  }
+}
+
+/// The unit vector of an axis, `None` for the zero vector
+fn normalize(x: f64, y: f64, z: f64) -> Option<[f64; 3]> {
+  let length = (x * x + y * y + z * z).sqrt();
+  (length > 0.0 && length.is_finite()).then(|| [x / length, y / length, z / length])
+}
+
+/// The matrix of a rotation around an axis, no rotation at all around the zero vector
+///
+/// https://www.w3.org/TR/css-transforms-2/#Rotate3dDefined
+fn rotation(x: f64, y: f64, z: f64, angle: f64) -> Matrix {
+  let [x, y, z] = match normalize(x, y, z) {
+    Some(axis) => axis,
+    None => return Matrix::IDENTITY,
+  };
+  let sc = (angle / 2.0).sin() * (angle / 2.0).cos();
+  let sq = (angle / 2.0).sin().powi(2);
+  Matrix([
+    1.0 - 2.0 * (y * y + z * z) * sq,
+    2.0 * (x * y * sq + z * sc),
+    2.0 * (x * z * sq - y * sc),
+    0.0,
+    2.0 * (x * y * sq - z * sc),
+    1.0 - 2.0 * (x * x + z * z) * sq,
+    2.0 * (y * z * sq + x * sc),
+    0.0,
+    2.0 * (x * z * sq + y * sc),
+    2.0 * (y * z * sq - x * sc),
+    1.0 - 2.0 * (x * x + y * y) * sq,
+    0.0,
+    0.0,
+    0.0,
+    0.0,
+    1.0,
+  ])
+}
@@ -5,17 +5,192 @@ pub struct Dimension<'a> {
   pub unit: &'a str,
 }
 
+/// The type flag of a number, as set by
+/// https://www.w3.org/TR/css-syntax-3/#consume-number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberType {
+  Integer,
+  /// The number has a fractional part or an exponent
+  Number,
+}
+
+/// An explicit leading sign of a number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sign {
+  Plus,
+  Minus,
+}
+
+/// The category of a CSS unit
+/// https://www.w3.org/TR/css-values-4/#lengths
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnitKind {
+  /// No unit at all, a plain `<number>`
+  Number,
+  /// `px`, `cm`, `mm`, `Q`, `in`, `pt`, `pc`
+  AbsoluteLength,
+  /// `em`, `rem`, `ex`, `ch`, `lh` and friends
+  FontRelativeLength,
+  /// `vw`, `vh`, `vmin`, ... including the `sv*`, `lv*` and `dv*` variants
+  ViewportLength,
+  /// `cqw`, `cqh`, `cqi`, `cqb`, `cqmin`, `cqmax`
+  ContainerLength,
+  /// `deg`, `grad`, `rad`, `turn`
+  Angle,
+  /// `s`, `ms`
+  Time,
+  /// `Hz`, `kHz`
+  Frequency,
+  /// `dpi`, `dpcm`, `dppx`, `x`
+  Resolution,
+  /// `fr`
+  Flex,
+  /// `%`
+  Percentage,
+  Unknown,
+}
+
+impl UnitKind {
+  /// Classify a unit, units are matched ASCII case-insensitively
+  pub fn of(unit: &str) -> UnitKind {
+    with_lowercase(unit, |lower| match lower {
+      b"" => UnitKind::Number,
+      b"px" | b"cm" | b"mm" | b"q" | b"in" | b"pt" | b"pc" => UnitKind::AbsoluteLength,
+      b"em" | b"rem" | b"ex" | b"rex" | b"cap" | b"rcap" | b"ch" | b"rch" | b"ic" | b"ric"
+      | b"lh" | b"rlh" => UnitKind::FontRelativeLength,
+      b"vw" | b"vh" | b"vi" | b"vb" | b"vmin" | b"vmax" | b"svw" | b"svh" | b"svi" | b"svb"
+      | b"svmin" | b"svmax" | b"lvw" | b"lvh" | b"lvi" | b"lvb" | b"lvmin" | b"lvmax" | b"dvw"
+      | b"dvh" | b"dvi" | b"dvb" | b"dvmin" | b"dvmax" => UnitKind::ViewportLength,
+      b"cqw" | b"cqh" | b"cqi" | b"cqb" | b"cqmin" | b"cqmax" => UnitKind::ContainerLength,
+      b"deg" | b"grad" | b"rad" | b"turn" => UnitKind::Angle,
+      b"s" | b"ms" => UnitKind::Time,
+      b"hz" | b"khz" => UnitKind::Frequency,
+      b"dpi" | b"dpcm" | b"dppx" | b"x" => UnitKind::Resolution,
+      b"fr" => UnitKind::Flex,
+      b"%" => UnitKind::Percentage,
+      _ => UnitKind::Unknown,
+    })
+    .unwrap_or(UnitKind::Unknown)
+  }
+
+  pub fn is_length(self) -> bool {
+    matches!(
+      self,
+      UnitKind::AbsoluteLength
+        | UnitKind::FontRelativeLength
+        | UnitKind::ViewportLength
+        | UnitKind::ContainerLength
+    )
+  }
+}
+
 impl<'a> Dimension<'a> {
   pub fn new(number: &'a str, unit: &'a str) -> Self {
     Self { number, unit }
   }
+
+  pub fn kind(&self) -> UnitKind {
+    UnitKind::of(self.unit)
+  }
+
+  /// The numeric value of `number`, NaN if it is not a valid CSS number
+  ///
+  /// Convert a string to a number
+  /// https://www.w3.org/TR/css-syntax-3/#convert-string-to-number
+  pub fn value(&self) -> f64 {
+    // `f64::from_str` accepts every number `unit` produces (`+.5`, `1e+3`) and
+    // rounds correctly, the text just has to be checked to be a CSS number.
+    match unit(self.number) {
+      Some(dimension) if dimension.unit.is_empty() => self.number.parse().unwrap_or(f64::NAN),
+      _ => f64::NAN,
+    }
+  }
+
+  pub fn number_type(&self) -> NumberType {
+    if self.number.bytes().any(|b| matches!(b, b'.' | b'e' | b'E')) {
+      NumberType::Number
+    } else {
+      NumberType::Integer
+    }
+  }
+
+  pub fn sign(&self) -> Option<Sign> {
+    match self.number.as_bytes().first() {
+      Some(b'+') => Some(Sign::Plus),
+      Some(b'-') => Some(Sign::Minus),
+      _ => None,
+    }
+  }
+}
+
+/// Run `f` with the ASCII lowercase form of a unit, `None` if it is too long to be a known unit
+#[inline]
+fn with_lowercase<R>(unit: &str, f: impl FnOnce(&[u8]) -> R) -> Option<R> {
+  // no known unit is longer than this
+  const MAX_LEN: usize = 5;
+  if unit.len() > MAX_LEN {
+    return None;
+  }
+  let mut buffer = [0u8; MAX_LEN];
+  let lower = &mut buffer[..unit.len()];
+  lower.copy_from_slice(unit.as_bytes());
+  lower.make_ascii_lowercase();
+  Some(f(lower))
+}
+
+/// The canonical unit a unit converts to, and how many canonical units one `unit` is.
+/// Only absolute lengths, angles, times, frequencies and resolutions are convertible.
+///
+/// https://www.w3.org/TR/css-values-4/#absolute-lengths
+pub fn canonical_unit(unit: &str) -> Option<(&'static str, f64)> {
+  use std::f64::consts::PI;
+
+  with_lowercase(unit, |lower| match lower {
+    b"px" => Some(("px", 1.0)),
+    b"in" => Some(("px", 96.0)),
+    b"cm" => Some(("px", 96.0 / 2.54)),
+    b"mm" => Some(("px", 96.0 / 25.4)),
+    b"q" => Some(("px", 96.0 / 101.6)),
+    b"pt" => Some(("px", 96.0 / 72.0)),
+    b"pc" => Some(("px", 96.0 / 6.0)),
+    b"deg" => Some(("deg", 1.0)),
+    b"grad" => Some(("deg", 0.9)),
+    b"rad" => Some(("deg", 180.0 / PI)),
+    b"turn" => Some(("deg", 360.0)),
+    b"s" => Some(("s", 1.0)),
+    b"ms" => Some(("s", 0.001)),
+    b"hz" => Some(("hz", 1.0)),
+    b"khz" => Some(("hz", 1000.0)),
+    b"dppx" | b"x" => Some(("dppx", 1.0)),
+    b"dpi" => Some(("dppx", 1.0 / 96.0)),
+    b"dpcm" => Some(("dppx", 2.54 / 96.0)),
+    _ => None,
+  })
+  .flatten()
+}
+
+/// Convert a dimension to `target_unit`, `None` if the units are not compatible
+pub fn convert(dimension: &Dimension, target_unit: &str) -> Option<f64> {
+  convert_value(dimension.value(), dimension.unit, target_unit)
+}
+
+pub(crate) fn convert_value(value: f64, unit: &str, target_unit: &str) -> Option<f64> {
+  if unit.eq_ignore_ascii_case(target_unit) {
+    return Some(value);
+  }
+  let (from, from_factor) = canonical_unit(unit)?;
+  let (to, to_factor) = canonical_unit(target_unit)?;
+  if from != to {
+    return None;
+  }
+  Some(value * from_factor / to_factor)
 }
 
 /// Decompose a CSS dimension into its numeric and unit part
 ///
 /// Consume a number
 /// https://www.w3.org/TR/css-syntax-3/#consume-number
-pub fn unit(value: &str) -> Option<Dimension> {
+pub fn unit(value: &str) -> Option<Dimension<'_>> {
   if !start_a_number(value) {
     return None;
   }
@@ -35,7 +210,7 @@ pub fn unit(value: &str) -> Option<Dimension> {
 
   // float number
   // the next 2 input code points are U+002E FULL STOP (.) followed by a digit
-  if matches!(value[pos..].as_bytes(), [b'.', b'0'..=b'9', ..]) {
+  if matches!(&value.as_bytes()[pos..], [b'.', b'0'..=b'9', ..]) {
     pos += 2;
     while matches!(value.as_bytes().get(pos), Some(b'0'..=b'9')) {
       pos += 1;
@@ -46,10 +221,10 @@ pub fn unit(value: &str) -> Option<Dimension> {
   // the next 2 or 3 input code points are U+0045 LATIN CAPITAL LETTER E (E) or U+0065 LATIN SMALL LETTER E (e),
   // optionally followed by U+002D HYPHEN-MINUS (-) or U+002B PLUS SIGN (+), followed by a digit
   {
-    if matches!(value[pos..].as_bytes(), [b'E' | b'e', b'0'..=b'9', ..]) {
+    if matches!(&value.as_bytes()[pos..], [b'E' | b'e', b'0'..=b'9', ..]) {
       pos += 2;
     } else if matches!(
-      value[pos..].as_bytes(),
+      &value.as_bytes()[pos..],
       [b'E' | b'e', b'+' | b'-', b'0'..=b'9', ..]
     ) {
       pos += 3;
@@ -67,7 +242,7 @@ pub fn unit(value: &str) -> Option<Dimension> {
 /// https://www.w3.org/TR/css-syntax-3/#starts-with-a-number
 #[inline]
 fn start_a_number(value: &str) -> bool {
-  match value.as_bytes().get(0) {
+  match value.as_bytes().first() {
     Some(b'+' | b'-') => match value.as_bytes().get(1) {
       Some(b'.') => matches!(value.as_bytes().get(2), Some(b'0'..=b'9')),
       Some(b'0'..=b'9') => true,
@@ -0,0 +1,302 @@
+use postcss_value_parser::calc::{
+  parse, parse_math_function, serialize, transform, transform_with_options, CalcError, Expr,
+  MathFunction, SimplifyOptions,
+};
+use postcss_value_parser::node::{stringify, Node};
+use postcss_value_parser::parser;
+use postcss_value_parser::quantity::{ArithmeticError, Quantity};
+
+fn calc_nodes(value: &str) -> Vec<Node<'_>> {
+  match parser::parse(value).pop() {
+    Some(Node::Function(function)) => function.nodes,
+    _ => panic!("{} is not a function", value),
+  }
+}
+
+fn simplify(value: &str) -> String {
+  serialize(&parse(&calc_nodes(value)).unwrap().simplify().unwrap())
+}
+
+fn minify(value: &str) -> String {
+  let mut nodes = parser::parse(value);
+  transform(&mut nodes);
+  stringify(&nodes)
+}
+
+#[test]
+fn parses_with_operator_precedence() {
+  assert_eq!(
+    parse(&calc_nodes("calc(1px + 2 * 3px / 4)")),
+    Ok(Expr::Sum(vec![
+      Expr::Value(Quantity::new(1.0, "px")),
+      Expr::Product(vec![
+        Expr::Value(Quantity::number(2.0)),
+        Expr::Value(Quantity::new(3.0, "px")),
+        Expr::Invert(Box::new(Expr::Value(Quantity::number(4.0)))),
+      ]),
+    ]))
+  );
+  assert_eq!(
+    parse(&calc_nodes("calc(1px - var(--a, 2px))")),
+    Ok(Expr::Sum(vec![
+      Expr::Value(Quantity::new(1.0, "px")),
+      Expr::Negate(Box::new(Expr::Opaque("var(--a, 2px)".into()))),
+    ]))
+  );
+}
+
+#[test]
+fn rejects_invalid_expressions() {
+  assert_eq!(
+    parse(&calc_nodes("calc((1px)- 2px)")),
+    Err(CalcError::MissingWhitespace("-".into()))
+  );
+  // `+2px` is a number with a sign, not an operator
+  assert_eq!(
+    parse(&calc_nodes("calc(1px +2px)")),
+    Err(CalcError::UnexpectedNode("+2px".into()))
+  );
+  assert_eq!(
+    parse(&calc_nodes("calc(1px *)")),
+    Err(CalcError::UnexpectedEnd)
+  );
+  assert_eq!(
+    parse(&calc_nodes("calc(1px 2px)")),
+    Err(CalcError::UnexpectedNode("2px".into()))
+  );
+  assert_eq!(
+    parse(&calc_nodes("calc(1px+2px)")),
+    Err(CalcError::UnexpectedNode("1px+2px".into()))
+  );
+  assert_eq!(
+    parse(&calc_nodes("calc(1px + 1s)")).unwrap().simplify(),
+    Err(CalcError::Arithmetic(ArithmeticError::IncompatibleUnits {
+      left: "px".into(),
+      right: "s".into()
+    }))
+  );
+  assert_eq!(
+    parse(&calc_nodes("calc(2px * 3 * 1em)"))
+      .unwrap()
+      .simplify(),
+    Err(CalcError::Arithmetic(ArithmeticError::IncompatibleUnits {
+      left: "px".into(),
+      right: "em".into()
+    }))
+  );
+  assert_eq!(minify("calc(1px * 1px)"), "calc(1px * 1px)");
+  assert_eq!(
+    parse(&calc_nodes("calc(1px * 2 / 1s)")).unwrap().simplify(),
+    Err(CalcError::Arithmetic(ArithmeticError::IncompatibleUnits {
+      left: "px".into(),
+      right: "s".into()
+    }))
+  );
+  assert_eq!(minify("calc(1px * 2 / 1s)"), "calc(1px * 2 / 1s)");
+  assert_eq!(minify("calc(2 / 1px)"), "calc(2 / 1px)");
+}
+
+#[test]
+fn simplifies_calculations() {
+  let tests = vec![
+    ("calc(10px + 2 * 5px)", "20px"),
+    ("calc(100% - 10px)", "calc(100% - 10px)"),
+    ("calc(1px + 2em + 3px)", "calc(4px + 2em)"),
+    ("calc(1px + (2em + 3px))", "calc(4px + 2em)"),
+    ("calc(1px - (2em - 3px))", "calc(4px - 2em)"),
+    ("calc(2 * (10% + 1em))", "calc(20% + 2em)"),
+    ("calc((10% + 1em) / 2)", "calc(5% + .5em)"),
+    (
+      "calc(100% / 3 - 2 * 1em)",
+      "calc(33.333333333333336% - 2em)",
+    ),
+    ("calc(1in + 4px)", "100px"),
+    ("calc(10px / 2px)", "5"),
+    ("calc(1 / 2px * 4px)", "2"),
+    ("calc(1 / 4)", ".25"),
+    ("calc(1s - 500ms)", ".5s"),
+    ("calc(calc(1px + 1px) * 2)", "4px"),
+    ("calc(/* a */ 1px /* b */ + 1px)", "2px"),
+    ("calc(var(--a) * 2 * 3)", "calc(6*var(--a))"),
+    ("calc(-1 * (var(--a) + 1px))", "calc(-1*(var(--a) + 1px))"),
+    (
+      "calc(var(--a) - 2 * var(--b))",
+      "calc(var(--a) - 2*var(--b))",
+    ),
+    (
+      "calc(var(--a) - 1px * var(--b))",
+      "calc(var(--a) - 1px*var(--b))",
+    ),
+    ("calc(10px - -5px)", "15px"),
+    ("-webkit-calc(1px + 1px)", "2px"),
+  ];
+
+  for (value, expected) in tests {
+    assert_eq!(simplify(value), expected, "{}", value);
+  }
+}
+
+#[test]
+fn transforms_calc_nodes() {
+  assert_eq!(
+    minify("calc(1px + 1px) solid calc(100% - 10px)"),
+    "2px solid calc(100% - 10px)"
+  );
+  assert_eq!(
+    minify("translate(calc(2 * 3px), calc(1px +1px))"),
+    "translate(6px, calc(1px +1px))"
+  );
+  assert_eq!(minify("calc(1px + calc(1em + 1em))"), "calc(1px + 2em)");
+}
+
+fn minify_with_root(value: &str, root_font_size: f64) -> String {
+  let mut nodes = parser::parse(value);
+  let options = SimplifyOptions {
+    root_font_size: Some(root_font_size),
+  };
+  transform_with_options(&mut nodes, &options);
+  stringify(&nodes)
+}
+
+#[test]
+fn parses_comparison_functions() {
+  let function = match parser::parse("min(10px, 2rem)").pop() {
+    Some(Node::Function(function)) => function,
+    _ => unreachable!(),
+  };
+  assert_eq!(
+    parse_math_function(&function),
+    Ok(Expr::Function(
+      MathFunction::Min,
+      vec![
+        Expr::Value(Quantity::new(10.0, "px")),
+        Expr::Value(Quantity::new(2.0, "rem")),
+      ]
+    ))
+  );
+  let function = match parser::parse("clamp(1px, 2px)").pop() {
+    Some(Node::Function(function)) => function,
+    _ => unreachable!(),
+  };
+  assert_eq!(
+    parse_math_function(&function),
+    Err(CalcError::ArgumentCount(MathFunction::Clamp))
+  );
+}
+
+#[test]
+fn evaluates_min_and_max() {
+  assert_eq!(minify("min(10px, 1in, 20px)"), "10px");
+  assert_eq!(minify("max(10px, 1in)"), "1in");
+  assert_eq!(minify("min(10px, 2rem)"), "min(10px, 2rem)");
+  assert_eq!(minify("min(10px, 2rem, 5px, 3rem)"), "min(5px,2rem)");
+  assert_eq!(minify("max(1px + 2px, var(--a))"), "max(3px,var(--a))");
+  assert_eq!(minify("calc(min(1px, 2px) * 2)"), "2px");
+  assert_eq!(minify_with_root("max(10px, 2rem)", 16.0), "2rem");
+  assert_eq!(minify_with_root("max(10px, 2rem)", 4.0), "10px");
+}
+
+#[test]
+fn evaluates_clamp() {
+  assert_eq!(minify("clamp(1px, 5px, 10px)"), "5px");
+  assert_eq!(minify("clamp(1px, 50px, 10px)"), "10px");
+  assert_eq!(minify("clamp(1px, 0px, 10px)"), "1px");
+  // the minimum wins when it is larger than the maximum
+  assert_eq!(minify("clamp(20px, 15px, 10px)"), "20px");
+  assert_eq!(minify("clamp(1rem, 5vw, 2rem)"), "clamp(1rem, 5vw, 2rem)");
+  assert_eq!(minify_with_root("clamp(1rem, 20px, 2rem)", 16.0), "20px");
+  assert_eq!(minify("clamp(1px, 2px)"), "clamp(1px, 2px)");
+}
+
+#[test]
+fn evaluates_rounding() {
+  assert_eq!(minify("round(2.5)"), "3");
+  assert_eq!(minify("round(-2.5)"), "-2");
+  assert_eq!(minify("round(17px, 5px)"), "15px");
+  assert_eq!(minify("round(up, 11px, 5px)"), "15px");
+  assert_eq!(minify("round(down, -11px, 5px)"), "-15px");
+  assert_eq!(minify("round(to-zero, -11px, 5px)"), "-10px");
+  assert_eq!(minify("round(nearest, 7.04px, 0.1px)"), "7px");
+  assert_eq!(minify("round(1in, 10px)"), "100px");
+  assert_eq!(minify("mod(1in, 10px)"), "6px");
+  assert_eq!(
+    minify("round(up, 1px, infinity * 1px)"),
+    "calc(infinity*1px)"
+  );
+  assert_eq!(minify("round(10px, 0px)"), "calc(NaN*1px)");
+  assert_eq!(minify("round(up, 10%, 3px)"), "round(up, 10%, 3px)");
+  assert_eq!(minify("mod(18px, 5px)"), "3px");
+  assert_eq!(minify("mod(-18px, 5px)"), "2px");
+  assert_eq!(minify("rem(-18px, 5px)"), "-3px");
+  assert_eq!(minify("mod(140deg, -90deg)"), "-40deg");
+}
+
+#[test]
+fn evaluates_signs_and_powers() {
+  assert_eq!(minify("abs(-2px)"), "2px");
+  assert_eq!(minify("abs(-20%)"), "abs(-20%)");
+  assert_eq!(minify("sign(-2px)"), "-1");
+  assert_eq!(minify("calc(sign(0) * 1px)"), "0px");
+  assert_eq!(minify("pow(2, 10)"), "1024");
+  assert_eq!(minify("sqrt(16)"), "4");
+  assert_eq!(minify("hypot(3px, 4px)"), "5px");
+  assert_eq!(minify("hypot(-5em)"), "5em");
+  assert_eq!(minify("log(8, 2)"), "3");
+  assert_eq!(minify("log(e)"), "1");
+  assert_eq!(minify("exp(0)"), "1");
+  assert_eq!(minify("calc(1px * pow(2, 3))"), "8px");
+}
+
+#[test]
+fn evaluates_trigonometry_with_angle_results() {
+  assert_eq!(minify("sin(90deg)"), "1");
+  assert_eq!(minify("cos(0)"), "1");
+  assert_eq!(minify("cos(.5turn)"), "-1");
+  assert_eq!(minify("tan(0rad)"), "0");
+  assert_eq!(minify("acos(-1)"), "180deg");
+  assert_eq!(minify("asin(1)"), "90deg");
+  assert_eq!(minify("atan2(1px, 0px)"), "90deg");
+  assert_eq!(minify("atan2(0, -1)"), "180deg");
+  assert_eq!(minify("rotate(calc(atan(0) + 1turn))"), "rotate(360deg)");
+}
+
+#[test]
+fn evaluates_constants() {
+  assert_eq!(minify("calc(pi)"), "3.141592653589793");
+  assert_eq!(minify("calc(e * 0)"), "0");
+  assert_eq!(minify("calc(infinity)"), "calc(infinity)");
+  assert_eq!(minify("calc(-infinity * 1px)"), "calc(-infinity*1px)");
+  assert_eq!(minify("calc(NaN)"), "calc(NaN)");
+  assert_eq!(minify("calc(1px / 0)"), "calc(infinity*1px)");
+  assert_eq!(
+    minify("calc(var(--x) * infinity * 1s)"),
+    "calc((infinity*1s)*var(--x))"
+  );
+}
+
+#[test]
+fn rejects_mistyped_math_arguments() {
+  let nodes = parser::parse("calc(sin(1px) + round(1px, 2) + pow(1, 2, 3))");
+  let function = match &nodes[0] {
+    Node::Function(function) => function,
+    _ => unreachable!(),
+  };
+  assert_eq!(
+    parse_math_function(function)
+      .and_then(Expr::simplify)
+      .unwrap_err(),
+    CalcError::ArgumentCount(MathFunction::Pow)
+  );
+  assert_eq!(
+    parse(&calc_nodes("calc(sin(1px))")).and_then(Expr::simplify),
+    Err(CalcError::ArgumentType(MathFunction::Sin))
+  );
+  assert_eq!(
+    parse(&calc_nodes("calc(round(1px, 2))")).and_then(Expr::simplify),
+    Err(CalcError::Arithmetic(ArithmeticError::IncompatibleUnits {
+      left: "".into(),
+      right: "px".into()
+    }))
+  );
+  assert_eq!(minify("sqrt(2px)"), "sqrt(2px)");
+}
@@ -0,0 +1,79 @@
+use postcss_value_parser::color::{AbsoluteColor, Color, ColorSpace};
+use postcss_value_parser::color_adjust::AdjustmentSpace;
+use postcss_value_parser::node::Node;
+
+fn absolute(value: &str) -> AbsoluteColor {
+  match Color::parse(value) {
+    Some(Color::Absolute(color)) => color,
+    _ => panic!("{} is not an absolute color", value),
+  }
+}
+
+/// The color as a hex code rounded to 8 bits, like Sass prints it
+fn hex(color: AbsoluteColor) -> String {
+  let srgb = color.to_space(ColorSpace::Srgb);
+  let mut hex = String::from("#");
+  for channel in srgb.components {
+    hex.push_str(&format!("{:02x}", (channel.unwrap() * 255.0).round() as u8));
+  }
+  hex
+}
+
+#[test]
+fn matches_sass_in_hsl() {
+  let hsl = AdjustmentSpace::Hsl;
+  assert_eq!(hex(absolute("#880000").lighten(0.2, hsl)), "#ee0000");
+  assert_eq!(hex(absolute("#ee0000").darken(0.2, hsl)), "#880000");
+  assert_eq!(hex(absolute("#885555").saturate(0.2, hsl)), "#9e3f3f");
+  assert_eq!(hex(absolute("#885555").desaturate(0.2, hsl)), "#726b6b");
+  assert_eq!(hex(absolute("#6b717f").adjust_hue(60.0, hsl)), "#796b7f");
+  assert_eq!(hex(absolute("#6b717f").grayscale(hsl)), "#757575");
+  assert_eq!(hex(absolute("#b37399").invert()), "#4c8c66");
+  assert_eq!(
+    hex(absolute("#ff0000").mix(&absolute("#0000ff"), 0.75, ColorSpace::Srgb)),
+    "#bf0040"
+  );
+  // lightness stops at white
+  assert_eq!(hex(absolute("#eeeeee").lighten(0.5, hsl)), "#ffffff");
+}
+
+#[test]
+fn adjusts_in_perceptual_spaces() {
+  let red = absolute("red");
+  let lighter = red.lighten(0.1, AdjustmentSpace::Oklch);
+  assert_eq!(lighter.space, ColorSpace::Srgb);
+  let [before, after] =
+    [red, lighter].map(|color| color.to_space(ColorSpace::Oklch).components[0].unwrap());
+  assert!((after - before - 0.1).abs() < 1e-9);
+
+  let gray = red.grayscale(AdjustmentSpace::Lch);
+  let lch = gray.to_space(ColorSpace::Lch);
+  assert!(lch.components[1].unwrap() < 1e-3);
+
+  let oklch = absolute("oklch(.5 .1 350)");
+  assert_eq!(
+    oklch.adjust_hue(20.0, AdjustmentSpace::Oklch).components,
+    [Some(0.5), Some(0.1), Some(10.0)]
+  );
+  assert_eq!(
+    oklch.desaturate(0.5, AdjustmentSpace::Oklch).components,
+    [Some(0.5), Some(0.0), Some(350.0)]
+  );
+}
+
+#[test]
+fn reserializes_through_nodes() {
+  let color = absolute("#3bbfce").with_alpha(0.5);
+  assert_eq!(color.alpha, Some(0.5));
+  assert_eq!(
+    Color::Absolute(absolute("red").with_alpha(2.0))
+      .to_node()
+      .to_string(),
+    "rgb(255 0 0)"
+  );
+  match Color::Absolute(color).to_node() {
+    Node::Function(function) => assert_eq!(function.value, "rgb"),
+    node => panic!("{:?}", node),
+  }
+  assert_eq!(Color::CurrentColor.to_node().to_string(), "currentcolor");
+}
@@ -0,0 +1,110 @@
+use postcss_value_parser::color::Color;
+use postcss_value_parser::color_min::{minify, transform, MinifyOptions};
+use postcss_value_parser::node::stringify;
+use postcss_value_parser::parser::parse;
+
+fn colormin(value: &str) -> String {
+  colormin_with_options(value, &MinifyOptions::default())
+}
+
+fn colormin_with_options(value: &str, options: &MinifyOptions) -> String {
+  let mut nodes = parse(value);
+  transform(&mut nodes, options);
+  stringify(&nodes)
+}
+
+#[test]
+fn minifies_colors() {
+  let tests = [
+    ("#ffffff", "#fff"),
+    ("#FFFFFF", "#fff"),
+    ("#f00f", "red"),
+    ("#ff0000", "red"),
+    ("rgb(255,0,0)", "red"),
+    ("rgba(255, 0, 0, 1)", "red"),
+    ("rgb(100% 0% 0%)", "red"),
+    ("rgba(0,0,0,0)", "transparent"),
+    // older browsers don't know hex codes with alpha
+    ("#0000", "#0000"),
+    ("#ff000080", "#ff000080"),
+    ("hsl(120, 100%, 50%)", "#0f0"),
+    ("hsl(0deg 0% 100%)", "#fff"),
+    ("hwb(0 0% 0%)", "red"),
+    ("white", "#fff"),
+    ("aqua", "aqua"),
+    ("#f0ffff", "azure"),
+    ("rgba(255,0,0,.5)", "rgba(255,0,0,.5)"),
+    ("rgb(255 0 0 / 50%)", "rgba(255,0,0,.5)"),
+    ("rgba(255, 0, 0, 0.2)", "rgba(255,0,0,.2)"),
+    ("color(srgb 1 0 0)", "red"),
+  ];
+  for (value, expected) in tests {
+    assert_eq!(colormin(value), expected, "{}", value);
+  }
+}
+
+#[test]
+fn keeps_colors_that_are_not_8_bit_srgb() {
+  let tests = [
+    "hsl(0 0% 33%)",
+    "rgb(10.5 0 0)",
+    "oklch(70% .1 150)",
+    "rgb(none 0 0)",
+    "rgb(0 0 0 / none)",
+    "color(display-p3 1 0 0)",
+    "currentcolor",
+    "tan",
+    "#abc",
+  ];
+  for value in tests {
+    assert_eq!(colormin(value), value);
+  }
+}
+
+#[test]
+fn can_use_hex_codes_with_alpha() {
+  let options = MinifyOptions {
+    alpha_hex: true,
+    ..Default::default()
+  };
+  assert_eq!(colormin_with_options("rgba(0,0,0,0)", &options), "#0000");
+  assert_eq!(
+    colormin_with_options("rgba(255, 0, 0, 0.2)", &options),
+    "#f003"
+  );
+  assert_eq!(
+    colormin_with_options("rgb(10 20 30 / 0.8)", &options),
+    "#0a141ecc"
+  );
+  assert_eq!(
+    colormin_with_options("rgba(255,0,0,.5)", &options),
+    "rgba(255,0,0,.5)"
+  );
+}
+
+#[test]
+fn can_avoid_named_colors() {
+  let options = MinifyOptions {
+    avoid_named_colors: true,
+    alpha_hex: true,
+  };
+  assert_eq!(colormin_with_options("#ff0000", &options), "#f00");
+  assert_eq!(colormin_with_options("rgba(0,0,0,0)", &options), "#0000");
+  assert_eq!(colormin_with_options("white", &options), "#fff");
+  assert_eq!(
+    minify(&Color::parse("#f00").unwrap(), &options),
+    Some("#f00".to_string())
+  );
+}
+
+#[test]
+fn walks_nodes() {
+  assert_eq!(
+    colormin("1px solid #ffffff, linear-gradient(rgb(0, 0, 255), hsla(0, 100%, 50%, 1))"),
+    "1px solid #fff, linear-gradient(#00f, red)"
+  );
+  assert_eq!(
+    colormin("var(--a, #aabbcc) url(#ffffff)"),
+    "var(--a, #abc) url(#ffffff)"
+  );
+}
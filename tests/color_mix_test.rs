@@ -0,0 +1,136 @@
+use postcss_value_parser::color::{AbsoluteColor, Color, ColorSpace};
+use postcss_value_parser::color_mix::{interpolate, transform, HueInterpolation};
+use postcss_value_parser::node::stringify;
+use postcss_value_parser::parser::parse;
+
+fn evaluate(value: &str) -> String {
+  let mut nodes = parse(value);
+  transform(&mut nodes);
+  stringify(&nodes)
+}
+
+fn absolute(value: &str) -> AbsoluteColor {
+  match Color::parse(value) {
+    Some(Color::Absolute(color)) => color,
+    _ => panic!("{} is not an absolute color", value),
+  }
+}
+
+#[test]
+fn mixes_colors() {
+  let tests = [
+    ("color-mix(in srgb, red, blue)", "rgb(127.5 0 127.5)"),
+    ("color-mix(in srgb, red 40%, blue)", "rgb(102 0 153)"),
+    ("color-mix(in srgb, red, 40% blue)", "rgb(153 0 102)"),
+    (
+      "color-mix(in srgb, red 20%, blue 20%)",
+      "rgb(127.5 0 127.5 / .4)",
+    ),
+    (
+      "color-mix(in srgb, red 60%, blue 60%)",
+      "rgb(127.5 0 127.5)",
+    ),
+    (
+      "color-mix(in srgb, rgb(255 0 0 / .5), blue)",
+      "rgb(85 0 170 / .75)",
+    ),
+    (
+      "color-mix(in hsl, hsl(10 100% 50%), hsl(350 100% 50%))",
+      "hsl(0 100% 50%)",
+    ),
+    (
+      "color-mix(in hsl longer hue, hsl(10 100% 50%), hsl(350 100% 50%))",
+      "hsl(180 100% 50%)",
+    ),
+    (
+      "color-mix(in hsl increasing hue, hsl(350 100% 50%), hsl(10 100% 50%))",
+      "hsl(0 100% 50%)",
+    ),
+    (
+      "color-mix(in hsl decreasing hue, hsl(350 100% 50%), hsl(10 100% 50%))",
+      "hsl(180 100% 50%)",
+    ),
+    (
+      "color-mix(in oklch, oklch(none .1 100), oklch(.5 .1 200))",
+      "oklch(.5 .1 150)",
+    ),
+    (
+      "color-mix(in oklab, oklab(.2 0 0), oklab(.6 .1 -.1))",
+      "oklab(.4 .05 -.05)",
+    ),
+    (
+      "color-mix(oklab(.2 0 0), oklab(.6 .1 -.1))",
+      "oklab(.4 .05 -.05)",
+    ),
+  ];
+  for (value, expected) in tests {
+    assert_eq!(evaluate(value), expected, "{}", value);
+  }
+  assert_eq!(
+    absolute("color-mix(in oklch, red 40%, blue)").space,
+    ColorSpace::Oklch
+  );
+}
+
+#[test]
+fn keeps_missing_components_of_analogous_spaces() {
+  // the missing hue of `hsl()` is the missing hue of `oklch()`
+  let mixed = interpolate(
+    &absolute("hsl(none 50% 50%)"),
+    &absolute("oklch(.5 .1 90)"),
+    0.25,
+    ColorSpace::Oklch,
+    HueInterpolation::Shorter,
+  );
+  assert_eq!(mixed.components[2], Some(90.0));
+}
+
+#[test]
+fn leaves_unknown_mixes() {
+  let tests = [
+    "color-mix(in srgb, var(--a), blue)",
+    "color-mix(in srgb, currentcolor, blue)",
+    "color-mix(in srgb, red 120%, blue)",
+    "color-mix(in srgb, red 0%, blue 0%)",
+    "color-mix(in srgb longer hue, red, blue)",
+    "color-mix(in nowhere, red, blue)",
+    "color-mix(in srgb, red, blue, green)",
+  ];
+  for value in tests {
+    assert_eq!(evaluate(value), value);
+  }
+}
+
+#[test]
+fn evaluates_relative_colors() {
+  let tests = [
+    ("rgb(from #336699 r g calc(b / 2))", "rgb(51 102 76.5)"),
+    ("rgb(from red r g b / calc(alpha / 2))", "rgb(255 0 0 / .5)"),
+    ("hsl(from red calc(h + 120) s l)", "hsl(120 100% 50%)"),
+    ("hwb(from hwb(10 20% 30%) h b w)", "hwb(10 30% 20%)"),
+    (
+      "oklch(from oklch(.5 .1 100) l calc(c * 2) none)",
+      "oklch(.5 .2 none)",
+    ),
+    ("lab(from lab(50 10 20) l b a / .5)", "lab(50 20 10 / .5)"),
+    (
+      "color(from color(display-p3 1 0 0) display-p3 r 0 b)",
+      "color(display-p3 1 0 0)",
+    ),
+    (
+      "color(from color(xyz .1 .2 .3) xyz z y x)",
+      "color(xyz-d65 .3 .2 .1)",
+    ),
+    (
+      "rgb(from color-mix(in srgb, red, blue) r g b)",
+      "rgb(127.5 0 127.5)",
+    ),
+    (
+      "1px solid rgb(from var(--x) r g b), rgb(from red 0 g b)",
+      "1px solid rgb(from var(--x) r g b), rgb(0 0 0)",
+    ),
+  ];
+  for (value, expected) in tests {
+    assert_eq!(evaluate(value), expected, "{}", value);
+  }
+}
@@ -0,0 +1,158 @@
+use postcss_value_parser::color::{AbsoluteColor, Color, ColorSpace};
+use postcss_value_parser::color_space::delta_e_ok;
+
+const SPACES: [ColorSpace; 14] = [
+  ColorSpace::Srgb,
+  ColorSpace::Hsl,
+  ColorSpace::Hwb,
+  ColorSpace::Lab,
+  ColorSpace::Lch,
+  ColorSpace::Oklab,
+  ColorSpace::Oklch,
+  ColorSpace::SrgbLinear,
+  ColorSpace::DisplayP3,
+  ColorSpace::A98Rgb,
+  ColorSpace::ProphotoRgb,
+  ColorSpace::Rec2020,
+  ColorSpace::XyzD50,
+  ColorSpace::XyzD65,
+];
+
+fn absolute(value: &str) -> AbsoluteColor {
+  match Color::parse(value) {
+    Some(Color::Absolute(color)) => color,
+    _ => panic!("{} is not an absolute color", value),
+  }
+}
+
+fn assert_components(color: &AbsoluteColor, expected: [Option<f64>; 3], tolerance: f64) {
+  for (actual, expected) in color.components.iter().zip(expected.iter()) {
+    match (actual, expected) {
+      (Some(actual), Some(expected)) => assert!(
+        (actual - expected).abs() < tolerance,
+        "{:?} is not {:?}",
+        color.components,
+        expected
+      ),
+      (actual, expected) => assert_eq!(actual, expected, "{:?}", color.components),
+    }
+  }
+}
+
+#[test]
+fn converts_within_the_srgb_family() {
+  let red = absolute("red");
+  assert_components(
+    &red.to_space(ColorSpace::Hsl),
+    [Some(0.0), Some(100.0), Some(50.0)],
+    1e-9,
+  );
+  assert_components(
+    &red.to_space(ColorSpace::Hwb),
+    [Some(0.0), Some(0.0), Some(0.0)],
+    1e-9,
+  );
+  assert_components(
+    &absolute("hwb(120 20% 30%)").to_space(ColorSpace::Srgb),
+    [Some(0.2), Some(0.7), Some(0.2)],
+    1e-9,
+  );
+  // gray has no hue
+  assert_components(
+    &absolute("#808080").to_space(ColorSpace::Hsl),
+    [None, Some(0.0), Some(50.19607843137255)],
+    1e-9,
+  );
+}
+
+#[test]
+fn converts_to_lab_and_oklab() {
+  let red = absolute("red");
+  assert_components(
+    &red.to_space(ColorSpace::Lab),
+    [Some(54.2905), Some(80.8049), Some(69.891)],
+    1e-3,
+  );
+  assert_components(
+    &red.to_space(ColorSpace::Oklab),
+    [Some(0.62796), Some(0.22486), Some(0.12585)],
+    1e-4,
+  );
+  assert_components(
+    &red.to_space(ColorSpace::Oklch),
+    [Some(0.62796), Some(0.25768), Some(29.2339)],
+    1e-3,
+  );
+  assert_components(
+    &absolute("white").to_space(ColorSpace::Oklch),
+    [Some(1.0), Some(0.0), None],
+    1e-6,
+  );
+  assert_components(
+    &absolute("color(display-p3 1 0 0)").to_space(ColorSpace::Srgb),
+    [Some(1.093), Some(-0.2267), Some(-0.1501)],
+    1e-3,
+  );
+}
+
+#[test]
+fn round_trips_between_every_pair_of_spaces() {
+  let colors = [
+    absolute("rebeccapurple"),
+    absolute("#12ab34cc"),
+    absolute("oklch(60% 0.1 250)"),
+  ];
+  for color in colors {
+    for from in SPACES {
+      let converted = color.to_space(from);
+      for to in SPACES {
+        let back = converted.to_space(to).to_space(ColorSpace::Srgb);
+        assert_components(&back, color.to_space(ColorSpace::Srgb).components, 1e-6);
+        assert_eq!(back.alpha, color.alpha);
+      }
+    }
+  }
+}
+
+#[test]
+fn maps_colors_into_the_gamut() {
+  let origin = absolute("oklch(70% 0.3 150)");
+  assert!(!origin.in_gamut(ColorSpace::Srgb));
+  let mapped = origin.to_gamut(ColorSpace::Srgb);
+  assert_eq!(mapped.space, ColorSpace::Srgb);
+  assert!(mapped.in_gamut(ColorSpace::Srgb));
+  // the chroma is reduced, the lightness and hue are about kept
+  let [lightness, chroma, hue] = mapped
+    .to_space(ColorSpace::Oklch)
+    .components
+    .map(Option::unwrap);
+  assert!((lightness - 0.7).abs() < 0.02, "{}", lightness);
+  assert!(chroma < 0.25, "{}", chroma);
+  assert!((hue - 150.0).abs() < 5.0, "{}", hue);
+  assert!(delta_e_ok(&mapped, &origin) < 0.15);
+
+  let p3_red = absolute("color(display-p3 1 0 0)");
+  assert!(p3_red.in_gamut(ColorSpace::DisplayP3));
+  assert!(p3_red.to_gamut(ColorSpace::Hsl).in_gamut(ColorSpace::Srgb));
+
+  assert_components(
+    &absolute("oklch(100% 0.3 150 / .5)").to_gamut(ColorSpace::Srgb),
+    [Some(1.0), Some(1.0), Some(1.0)],
+    1e-6,
+  );
+  assert_eq!(
+    absolute("lab(200 0 0 / .5)")
+      .to_gamut(ColorSpace::Srgb)
+      .alpha,
+    Some(0.5)
+  );
+  // colors within the gamut are converted as they are
+  assert_components(
+    &absolute("#123456").to_gamut(ColorSpace::Srgb),
+    absolute("#123456").components,
+    1e-12,
+  );
+  // there are no limits to map into
+  let lab = absolute("color(rec2020 0 1 0)").to_gamut(ColorSpace::Lab);
+  assert_eq!(lab.space, ColorSpace::Lab);
+}
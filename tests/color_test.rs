@@ -0,0 +1,203 @@
+use postcss_value_parser::color::{named_color, AbsoluteColor, Color, ColorSpace};
+use postcss_value_parser::parser::parse;
+
+fn rgb(red: u8, green: u8, blue: u8, alpha: f64) -> Option<Color> {
+  Some(Color::Absolute(AbsoluteColor::srgb(
+    red as f64 / 255.0,
+    green as f64 / 255.0,
+    blue as f64 / 255.0,
+    alpha,
+  )))
+}
+
+fn hsl(hue: f64, saturation: f64, lightness: f64, alpha: f64) -> Option<Color> {
+  Some(Color::Absolute(AbsoluteColor::new(
+    ColorSpace::Hsl,
+    [hue, saturation, lightness],
+    alpha,
+  )))
+}
+
+#[test]
+fn parses_hex_colors() {
+  assert_eq!(Color::parse("#f00"), rgb(255, 0, 0, 1.0));
+  assert_eq!(Color::parse("#F008"), rgb(255, 0, 0, 136.0 / 255.0));
+  assert_eq!(Color::parse("#663399"), rgb(102, 51, 153, 1.0));
+  assert_eq!(Color::parse("#66339900"), rgb(102, 51, 153, 0.0));
+  assert_eq!(Color::parse("#12345"), None);
+  assert_eq!(Color::parse("#ggg"), None);
+}
+
+#[test]
+fn parses_keywords() {
+  assert_eq!(Color::parse("RebeccaPurple"), rgb(102, 51, 153, 1.0));
+  assert_eq!(Color::parse("yellowgreen"), rgb(154, 205, 50, 1.0));
+  assert_eq!(Color::parse("aliceblue"), rgb(240, 248, 255, 1.0));
+  assert_eq!(Color::parse("transparent"), rgb(0, 0, 0, 0.0));
+  assert_eq!(Color::parse("currentColor"), Some(Color::CurrentColor));
+  assert_eq!(Color::parse("grey"), Color::parse("gray"));
+  assert_eq!(Color::parse("reddish"), None);
+  assert_eq!(named_color("none"), None);
+}
+
+#[test]
+fn parses_rgb_functions() {
+  assert_eq!(Color::parse("rgb(255, 0, 0)"), rgb(255, 0, 0, 1.0));
+  assert_eq!(Color::parse("rgba(255,0,0,.5)"), rgb(255, 0, 0, 0.5));
+  assert_eq!(Color::parse("rgb(100%, 0%, 0%, 50%)"), rgb(255, 0, 0, 0.5));
+  assert_eq!(Color::parse("rgb(255 0 0)"), rgb(255, 0, 0, 1.0));
+  assert_eq!(Color::parse("rgba(255 0 0 / 20%)"), rgb(255, 0, 0, 0.2));
+  assert_eq!(Color::parse("rgb(100% 0 0 / 2)"), rgb(255, 0, 0, 1.0));
+  assert_eq!(
+    Color::parse("rgb(300 -1 calc(255 / 5))"),
+    rgb(255, 0, 51, 1.0)
+  );
+  assert_eq!(
+    Color::parse("rgb(none 0 0 / none)"),
+    Some(Color::Absolute(AbsoluteColor {
+      space: ColorSpace::Srgb,
+      components: [None, Some(0.0), Some(0.0)],
+      alpha: None,
+    }))
+  );
+  // the legacy syntax takes neither mixed types nor `none`
+  assert_eq!(Color::parse("rgb(100%, 0, 0)"), None);
+  assert_eq!(Color::parse("rgb(none, 0, 0)"), None);
+  assert_eq!(Color::parse("rgb(255, 0 0)"), None);
+  assert_eq!(Color::parse("rgb(255 0 0 0)"), None);
+  assert_eq!(Color::parse("rgb(255 0 0 /)"), None);
+  assert_eq!(Color::parse("rgb(255, 0, 0,)"), None);
+  assert_eq!(Color::parse("rgb(var(--r) 0 0)"), None);
+  assert_eq!(Color::parse("rgb(255px 0 0)"), None);
+}
+
+#[test]
+fn parses_hsl_functions() {
+  assert_eq!(
+    Color::parse("hsl(120, 100%, 50%)"),
+    hsl(120.0, 100.0, 50.0, 1.0)
+  );
+  assert_eq!(
+    Color::parse("hsla(.5turn, 10%, 20%, .5)"),
+    hsl(180.0, 10.0, 20.0, 0.5)
+  );
+  assert_eq!(
+    Color::parse("hsl(120deg 100 50 / 25%)"),
+    hsl(120.0, 100.0, 50.0, 0.25)
+  );
+  assert_eq!(
+    Color::parse("hsl(120 150% -5%)"),
+    hsl(120.0, 100.0, 0.0, 1.0)
+  );
+  assert_eq!(Color::parse("hsl(120, 100, 50)"), None);
+  assert_eq!(Color::parse("hsl(120px 100% 50%)"), None);
+}
+
+#[test]
+fn reads_colors_from_nodes() {
+  let nodes = parse("1px solid #fff, 0 0 2px hsl(0 0% 0%)");
+  let colors = nodes
+    .iter()
+    .filter_map(Color::from_node)
+    .collect::<Vec<_>>();
+  assert_eq!(
+    colors,
+    vec![
+      rgb(255, 255, 255, 1.0).unwrap(),
+      hsl(0.0, 0.0, 0.0, 1.0).unwrap()
+    ]
+  );
+}
+
+fn color(space: ColorSpace, components: [f64; 3], alpha: f64) -> Option<Color> {
+  Some(Color::Absolute(AbsoluteColor::new(
+    space, components, alpha,
+  )))
+}
+
+#[test]
+fn parses_modern_color_functions() {
+  assert_eq!(
+    Color::parse("lab(50% 40 -20.5)"),
+    color(ColorSpace::Lab, [50.0, 40.0, -20.5], 1.0)
+  );
+  assert_eq!(
+    Color::parse("lch(120 50% .5turn / .5)"),
+    color(ColorSpace::Lch, [100.0, 75.0, 180.0], 0.5)
+  );
+  assert_eq!(
+    Color::parse("oklab(40% -100% 0.1)"),
+    color(ColorSpace::Oklab, [0.4, -0.4, 0.1], 1.0)
+  );
+  assert_eq!(
+    Color::parse("OKLCH(0.7 -0.1 200deg)"),
+    color(ColorSpace::Oklch, [0.7, 0.0, 200.0], 1.0)
+  );
+  assert_eq!(
+    Color::parse("hwb(90 10% 20)"),
+    color(ColorSpace::Hwb, [90.0, 10.0, 20.0], 1.0)
+  );
+  assert_eq!(
+    Color::parse("oklch(none 0.2 none / 50%)"),
+    Some(Color::Absolute(AbsoluteColor {
+      space: ColorSpace::Oklch,
+      components: [None, Some(0.2), None],
+      alpha: Some(0.5),
+    }))
+  );
+  assert_eq!(Color::parse("lab(50, 40, 20)"), None);
+  assert_eq!(Color::parse("lch(50 40 20px)"), None);
+  assert_eq!(Color::parse("oklab(50% 0)"), None);
+}
+
+#[test]
+fn parses_predefined_color_spaces() {
+  assert_eq!(
+    Color::parse("color(display-p3 1 0.5 0)"),
+    color(ColorSpace::DisplayP3, [1.0, 0.5, 0.0], 1.0)
+  );
+  assert_eq!(
+    Color::parse("color(srgb-linear 100% 50% 0% / .2)"),
+    color(ColorSpace::SrgbLinear, [1.0, 0.5, 0.0], 0.2)
+  );
+  assert_eq!(
+    Color::parse("color(rec2020 0 1.2 none)"),
+    Some(Color::Absolute(AbsoluteColor {
+      space: ColorSpace::Rec2020,
+      components: [Some(0.0), Some(1.2), None],
+      alpha: Some(1.0),
+    }))
+  );
+  assert_eq!(
+    Color::parse("color(xyz 0.2 0.3 0.4)"),
+    color(ColorSpace::XyzD65, [0.2, 0.3, 0.4], 1.0)
+  );
+  assert_eq!(
+    Color::parse("color(xyz-d50 0.2 0.3 0.4)"),
+    color(ColorSpace::XyzD50, [0.2, 0.3, 0.4], 1.0)
+  );
+  assert_eq!(Color::parse("color(srgb 1 0 0)"), rgb(255, 0, 0, 1.0));
+  assert_eq!(Color::parse("color(unknown 1 0 0)"), None);
+  assert_eq!(Color::parse("color(display-p3 1, 0, 0)"), None);
+}
+
+#[test]
+fn serializes_colors() {
+  let tests = [
+    ("#663399", "rgb(102 51 153)"),
+    ("rgba(255, 0, 0, .5)", "rgb(255 0 0 / .5)"),
+    ("hsl(120deg 100% 50% / none)", "hsl(120 100% 50% / none)"),
+    ("hwb(90 10% 20%)", "hwb(90 10% 20%)"),
+    ("lab(50% 40 -20.5)", "lab(50 40 -20.5)"),
+    ("oklch(70% 0.1 none)", "oklch(.7 .1 none)"),
+    ("color(display-p3 1 .5 0)", "color(display-p3 1 .5 0)"),
+    ("color(srgb 1.5 0 0)", "color(srgb 1.5 0 0)"),
+    ("color(xyz .2 .3 .4)", "color(xyz-d65 .2 .3 .4)"),
+    ("CurrentColor", "currentcolor"),
+  ];
+  for (value, expected) in tests {
+    let color = Color::parse(value).unwrap();
+    assert_eq!(color.to_string(), expected);
+    assert_eq!(Color::parse(&color.to_string()), Some(color), "{}", value);
+  }
+}
@@ -0,0 +1,129 @@
+use postcss_value_parser::color::{AbsoluteColor, Color};
+use postcss_value_parser::contrast::{
+  apca_contrast, contrast_ratio, meets_wcag, relative_luminance, WcagLevel,
+};
+
+fn absolute(value: &str) -> AbsoluteColor {
+  match Color::parse(value) {
+    Some(Color::Absolute(color)) => color,
+    _ => panic!("{} is not an absolute color", value),
+  }
+}
+
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+  assert!(
+    (actual - expected).abs() < tolerance,
+    "{} is not {}",
+    actual,
+    expected
+  );
+}
+
+#[test]
+fn computes_relative_luminance() {
+  assert_close(relative_luminance(&absolute("white")), 1.0, 1e-12);
+  assert_close(relative_luminance(&absolute("black")), 0.0, 1e-12);
+  assert_close(relative_luminance(&absolute("#808080")), 0.2158605, 1e-6);
+  assert_close(
+    relative_luminance(&absolute("hsl(120 100% 50%)")),
+    0.7152,
+    1e-12,
+  );
+}
+
+#[test]
+fn computes_wcag_contrast_ratios() {
+  assert_close(
+    contrast_ratio(&absolute("black"), &absolute("white")),
+    21.0,
+    1e-9,
+  );
+  assert_close(
+    contrast_ratio(&absolute("white"), &absolute("black")),
+    21.0,
+    1e-9,
+  );
+  assert_close(
+    contrast_ratio(&absolute("#777"), &absolute("white")),
+    4.478,
+    1e-3,
+  );
+  assert_close(
+    contrast_ratio(&absolute("red"), &absolute("red")),
+    1.0,
+    1e-12,
+  );
+
+  assert!(!meets_wcag(
+    &absolute("#777"),
+    &absolute("#fff"),
+    WcagLevel::AA,
+    false
+  ));
+  assert!(meets_wcag(
+    &absolute("#777"),
+    &absolute("#fff"),
+    WcagLevel::AA,
+    true
+  ));
+  assert!(meets_wcag(
+    &absolute("#767676"),
+    &absolute("#fff"),
+    WcagLevel::AA,
+    false
+  ));
+  assert!(!meets_wcag(
+    &absolute("#767676"),
+    &absolute("#fff"),
+    WcagLevel::AAA,
+    false
+  ));
+}
+
+#[test]
+fn composites_translucent_colors() {
+  let half_black = absolute("rgb(0 0 0 / .5)");
+  let composited = half_black.composite_over(&absolute("white"));
+  assert_eq!(composited.alpha, Some(1.0));
+  assert_close(composited.components[0].unwrap(), 0.5, 1e-12);
+  // translucent text is composited over the background before measuring
+  assert_close(
+    contrast_ratio(&half_black, &absolute("white")),
+    contrast_ratio(&absolute("rgb(127.5 127.5 127.5)"), &absolute("white")),
+    1e-9,
+  );
+  // and a translucent background over white
+  assert_close(
+    contrast_ratio(&absolute("black"), &absolute("transparent")),
+    21.0,
+    1e-9,
+  );
+  let both = absolute("rgb(255 0 0 / .5)").composite_over(&absolute("rgb(0 0 255 / .5)"));
+  assert_close(both.alpha.unwrap(), 0.75, 1e-12);
+  assert_close(both.components[0].unwrap(), 2.0 / 3.0, 1e-12);
+}
+
+#[test]
+fn computes_apca_contrast() {
+  assert_close(
+    apca_contrast(&absolute("black"), &absolute("white")),
+    106.04,
+    0.01,
+  );
+  assert_close(
+    apca_contrast(&absolute("white"), &absolute("black")),
+    -107.88,
+    0.01,
+  );
+  assert_close(
+    apca_contrast(&absolute("#888"), &absolute("#fff")),
+    63.06,
+    0.01,
+  );
+  assert_close(
+    apca_contrast(&absolute("#fff"), &absolute("#888")),
+    -68.54,
+    0.01,
+  );
+  assert_eq!(apca_contrast(&absolute("#777"), &absolute("#777")), 0.0);
+}
@@ -0,0 +1,127 @@
+use postcss_value_parser::gradient_legacy::transform;
+use postcss_value_parser::node::stringify;
+use postcss_value_parser::parser::parse;
+
+fn standard(value: &str) -> String {
+  let mut nodes = parse(value);
+  transform(&mut nodes);
+  stringify(&nodes)
+}
+
+#[test]
+fn converts_webkit_gradient() {
+  let tests = [
+    (
+      "-webkit-gradient(linear, left top, left bottom, from(#fff), to(#000))",
+      "linear-gradient(#fff, #000)",
+    ),
+    (
+      "-webkit-gradient(linear, left bottom, left top, from(#fff), to(#000))",
+      "linear-gradient(#000, #fff)",
+    ),
+    (
+      "-webkit-gradient(linear, 0% 0%, 100% 0%, from(red), color-stop(.25, rgba(0, 0, 0, .5)), to(blue))",
+      "linear-gradient(to right, red, rgba(0, 0, 0, .5) 25%, blue)",
+    ),
+    (
+      "-webkit-gradient(linear, left top, left 50%, color-stop(50%, red), from(blue))",
+      "linear-gradient(blue, red 25%)",
+    ),
+    (
+      "-webkit-gradient(linear, 0 0, 0 200, from(red), to(blue))",
+      "linear-gradient(red 0px, blue 200px)",
+    ),
+    (
+      "-webkit-gradient(linear, right bottom, left top, from(red), to(blue))",
+      "linear-gradient(to top left, red, blue)",
+    ),
+    (
+      "-webkit-gradient(radial, center center, 0, center center, 50, from(red), to(blue))",
+      "radial-gradient(circle 50px, red, blue)",
+    ),
+    (
+      "-webkit-gradient(radial, 10 20%, 10, 10 20%, 30, from(red), to(blue))",
+      "radial-gradient(circle 30px at 10px 20%, red 10px, blue 30px)",
+    ),
+  ];
+  for (legacy, expected) in tests {
+    assert_eq!(standard(legacy), expected, "{}", legacy);
+  }
+}
+
+#[test]
+fn leaves_what_can_not_be_converted() {
+  for value in [
+    // a diagonal that is not from corner to corner
+    "-webkit-gradient(linear, left top, 50% 100%, from(red), to(blue))",
+    // circles that are not concentric
+    "-webkit-gradient(radial, 0 0, 0, 10 10, 50, from(red), to(blue))",
+    "-webkit-gradient(linear, left top, left bottom)",
+    "-webkit-gradient(conic, left top, left bottom, from(red))",
+    "-webkit-linear-gradient(calc(var(--a)), red, blue)",
+    "linear-gradient(to right, red, blue)",
+  ] {
+    assert_eq!(standard(value), value);
+  }
+}
+
+#[test]
+fn converts_prefixed_gradients() {
+  let tests = [
+    (
+      "-webkit-linear-gradient(top, #fff, #000)",
+      "linear-gradient(#fff, #000)",
+    ),
+    (
+      "-moz-linear-gradient(left, red 10%, blue)",
+      "linear-gradient(to right, red 10%, blue)",
+    ),
+    (
+      "-webkit-linear-gradient(bottom right, red, blue)",
+      "linear-gradient(to top left, red, blue)",
+    ),
+    (
+      "-webkit-linear-gradient(0deg, red, blue)",
+      "linear-gradient(90deg, red, blue)",
+    ),
+    (
+      "-o-linear-gradient(45deg, red, blue)",
+      "linear-gradient(45deg, red, blue)",
+    ),
+    (
+      "-webkit-linear-gradient(.5turn, red, blue)",
+      "linear-gradient(270deg, red, blue)",
+    ),
+    (
+      "-webkit-linear-gradient(red, blue)",
+      "linear-gradient(red, blue)",
+    ),
+    (
+      "-webkit-repeating-linear-gradient(left, red 0 10px, blue 20px)",
+      "repeating-linear-gradient(to right, red 0 10px, blue 20px)",
+    ),
+    (
+      "-webkit-radial-gradient(50% 10%, circle cover, red, blue)",
+      "radial-gradient(circle farthest-corner at 50% 10%, red, blue)",
+    ),
+    (
+      "-webkit-radial-gradient(center, red, blue)",
+      "radial-gradient(at center, red, blue)",
+    ),
+  ];
+  for (legacy, expected) in tests {
+    assert_eq!(standard(legacy), expected, "{}", legacy);
+  }
+}
+
+#[test]
+fn converts_nested_gradients() {
+  assert_eq!(
+    standard("url(a.png), -webkit-linear-gradient(top, red, blue) no-repeat"),
+    "url(a.png), linear-gradient(red, blue) no-repeat"
+  );
+  assert_eq!(
+    standard("image-set(-webkit-linear-gradient(left, red, blue) 1x)"),
+    "image-set(linear-gradient(to right, red, blue) 1x)"
+  );
+}
@@ -0,0 +1,279 @@
+use postcss_value_parser::calc::Expr;
+use postcss_value_parser::color::{Color, ColorSpace};
+use postcss_value_parser::color_mix::HueInterpolation;
+use postcss_value_parser::gradient::{
+  Gradient, GradientItem, GradientKind, HorizontalSide, LineDirection, Position, PositionComponent,
+  PositionKeyword, RadialExtent, RadialShape, RadialSize, VendorPrefix, VerticalSide,
+};
+use postcss_value_parser::parser::parse;
+use postcss_value_parser::quantity::Quantity;
+
+fn value(value: f64, unit: &str) -> Expr {
+  Expr::Value(Quantity::new(value, unit))
+}
+
+/// Parse a gradient and hand it to `check`
+fn with_gradient(value: &str, check: impl FnOnce(Option<Gradient>)) {
+  let nodes = parse(value);
+  check(Gradient::from_node(&nodes[0]))
+}
+
+fn stops(gradient: &Gradient) -> Vec<String> {
+  gradient
+    .items
+    .iter()
+    .map(|item| match item {
+      GradientItem::Stop(stop) => {
+        let mut stop_text = stop.color.to_string();
+        for position in &stop.positions {
+          stop_text.push_str(&format!(" {}", position));
+        }
+        stop_text
+      }
+      GradientItem::Hint(hint) => format!("hint {}", hint),
+    })
+    .collect()
+}
+
+#[test]
+fn parses_linear_gradients() {
+  with_gradient("linear-gradient(red, blue)", |gradient| {
+    let gradient = gradient.unwrap();
+    assert_eq!(gradient.kind, GradientKind::Linear(None));
+    assert!(!gradient.repeating && gradient.prefix.is_none());
+    assert_eq!(gradient.interpolation, None);
+    assert_eq!(stops(&gradient), ["red", "blue"]);
+  });
+  with_gradient(
+    "linear-gradient(45deg, red 10%, 30%, blue 50% 80%)",
+    |gradient| {
+      let gradient = gradient.unwrap();
+      assert_eq!(
+        gradient.kind,
+        GradientKind::Linear(Some(LineDirection::Angle(value(45.0, "deg"))))
+      );
+      assert_eq!(stops(&gradient), ["red 10%", "hint 30%", "blue 50% 80%"]);
+    },
+  );
+  with_gradient(
+    "linear-gradient(to top left, #fff, rgb(0 0 0))",
+    |gradient| {
+      let gradient = gradient.unwrap();
+      assert_eq!(
+        gradient.kind,
+        GradientKind::Linear(Some(LineDirection::Side {
+          horizontal: Some(HorizontalSide::Left),
+          vertical: Some(VerticalSide::Top),
+        }))
+      );
+      match &gradient.items[1] {
+        GradientItem::Stop(stop) => {
+          assert_eq!(stop.color(), Color::parse("black"));
+        }
+        item => panic!("{:?}", item),
+      }
+    },
+  );
+  with_gradient(
+    "repeating-linear-gradient(in oklch longer hue to right, red 0 calc(10px + 5%), blue)",
+    |gradient| {
+      let gradient = gradient.unwrap();
+      assert!(gradient.repeating);
+      assert_eq!(
+        gradient.interpolation,
+        Some((ColorSpace::Oklch, HueInterpolation::Longer))
+      );
+      assert_eq!(stops(&gradient), ["red 0 10px + 5%", "blue"]);
+    },
+  );
+  with_gradient("linear-gradient(0, var(--from) 10%, blue)", |gradient| {
+    let gradient = gradient.unwrap();
+    assert_eq!(
+      gradient.kind,
+      GradientKind::Linear(Some(LineDirection::Angle(value(0.0, ""))))
+    );
+    assert_eq!(stops(&gradient), ["var(--from) 10%", "blue"]);
+  });
+}
+
+#[test]
+fn parses_prefixed_gradients() {
+  with_gradient("-webkit-linear-gradient(left, red, blue)", |gradient| {
+    let gradient = gradient.unwrap();
+    assert_eq!(gradient.prefix, Some(VendorPrefix::Webkit));
+    assert_eq!(
+      gradient.kind,
+      GradientKind::Linear(Some(LineDirection::Side {
+        horizontal: Some(HorizontalSide::Left),
+        vertical: None,
+      }))
+    );
+  });
+  with_gradient(
+    "-webkit-repeating-radial-gradient(50% 50%, circle cover, red, blue)",
+    |gradient| {
+      let gradient = gradient.unwrap();
+      assert!(gradient.prefix == Some(VendorPrefix::Webkit) && gradient.repeating);
+      match &gradient.kind {
+        GradientKind::Radial(radial) => {
+          assert_eq!(radial.shape, Some(RadialShape::Circle));
+          assert_eq!(
+            radial.size,
+            Some(RadialSize::Extent(RadialExtent::FarthestCorner))
+          );
+          assert_eq!(
+            radial.position,
+            Some(Position(vec![
+              PositionComponent::Value(value(50.0, "%")),
+              PositionComponent::Value(value(50.0, "%")),
+            ]))
+          );
+        }
+        kind => panic!("{:?}", kind),
+      }
+      assert_eq!(stops(&gradient), ["red", "blue"]);
+    },
+  );
+  with_gradient(
+    "-moz-radial-gradient(ellipse contain, red, blue)",
+    |gradient| match gradient.unwrap().kind {
+      GradientKind::Radial(radial) => assert_eq!(
+        radial.size,
+        Some(RadialSize::Extent(RadialExtent::ClosestSide))
+      ),
+      kind => panic!("{:?}", kind),
+    },
+  );
+  // the direction of a prefixed gradient has no `to`
+  with_gradient("-webkit-linear-gradient(to left, red, blue)", |gradient| {
+    assert_eq!(gradient, None)
+  });
+}
+
+#[test]
+fn parses_radial_gradients() {
+  with_gradient(
+    "radial-gradient(circle 10px at left 10px top, red, blue)",
+    |gradient| match gradient.unwrap().kind {
+      GradientKind::Radial(radial) => {
+        assert_eq!(radial.shape, Some(RadialShape::Circle));
+        assert_eq!(radial.size, Some(RadialSize::Circle(value(10.0, "px"))));
+        assert_eq!(
+          radial.position,
+          Some(Position(vec![
+            PositionComponent::Keyword(PositionKeyword::Left),
+            PositionComponent::Value(value(10.0, "px")),
+            PositionComponent::Keyword(PositionKeyword::Top),
+          ]))
+        );
+      }
+      kind => panic!("{:?}", kind),
+    },
+  );
+  with_gradient(
+    "radial-gradient(20% 30px, red, blue)",
+    |gradient| match gradient.unwrap().kind {
+      GradientKind::Radial(radial) => assert_eq!(
+        radial.size,
+        Some(RadialSize::Ellipse(value(20.0, "%"), value(30.0, "px")))
+      ),
+      kind => panic!("{:?}", kind),
+    },
+  );
+  with_gradient(
+    "radial-gradient(farthest-side in hsl, red, blue)",
+    |gradient| {
+      let gradient = gradient.unwrap();
+      assert_eq!(
+        gradient.interpolation,
+        Some((ColorSpace::Hsl, HueInterpolation::Shorter))
+      );
+      match gradient.kind {
+        GradientKind::Radial(radial) => {
+          assert_eq!(radial.shape, None);
+          assert_eq!(
+            radial.size,
+            Some(RadialSize::Extent(RadialExtent::FarthestSide))
+          );
+        }
+        kind => panic!("{:?}", kind),
+      }
+    },
+  );
+}
+
+#[test]
+fn parses_conic_gradients() {
+  with_gradient(
+    "conic-gradient(from .25turn at center, red 0deg 90deg, blue 90deg)",
+    |gradient| {
+      let gradient = gradient.unwrap();
+      match &gradient.kind {
+        GradientKind::Conic(conic) => {
+          assert_eq!(conic.angle, Some(value(0.25, "turn")));
+          assert_eq!(
+            conic.position,
+            Some(Position(vec![PositionComponent::Keyword(
+              PositionKeyword::Center
+            )]))
+          );
+        }
+        kind => panic!("{:?}", kind),
+      }
+      assert_eq!(stops(&gradient), ["red 0deg 90deg", "blue 90deg"]);
+    },
+  );
+}
+
+#[test]
+fn rejects_invalid_gradients() {
+  for invalid in [
+    "linear-gradient()",
+    "linear-gradient(, red)",
+    "radial-gradient(/* a */, red, blue)",
+    "linear-gradient(to, red, blue)",
+    "linear-gradient(to left right, red, blue)",
+    "linear-gradient(10px, red, blue)",
+    "linear-gradient(red, 10%)",
+    "linear-gradient(10%, red, blue)",
+    "linear-gradient(red, 10%, 20%, blue)",
+    "linear-gradient(red 1% 2% 3%, blue)",
+    "linear-gradient(red 50, blue)",
+    "linear-gradient(red, 50, blue)",
+    "linear-gradient(red 10deg, blue)",
+    "conic-gradient(red 10px, blue)",
+    "linear-gradient(to in oklab right, red, blue)",
+    "linear-gradient(in nowhere, red, blue)",
+    "radial-gradient(circle 10%, red, blue)",
+    "radial-gradient(circle 1px 2px, red, blue)",
+    "radial-gradient(at, red, blue)",
+    "-webkit-conic-gradient(red, blue)",
+    "linear-gradient(red, blue",
+    "rgb(red, blue)",
+  ] {
+    with_gradient(invalid, |gradient| {
+      assert_eq!(gradient, None, "{}", invalid)
+    });
+  }
+}
+
+#[test]
+fn serializes_gradients() {
+  for value in [
+    "linear-gradient(red, blue)",
+    "repeating-linear-gradient(to bottom right in oklch longer hue, red 0 calc(10px + 5%), 40%, blue)",
+    "radial-gradient(ellipse 20% 30px at left 10px top, var(--a), blue)",
+    "radial-gradient(closest-side in srgb, red, blue)",
+    "linear-gradient(in oklab, red, blue)",
+    "conic-gradient(from 90deg at 10% 20%, red 0deg 90deg, blue)",
+    "-webkit-linear-gradient(top left, red, blue)",
+    "-webkit-radial-gradient(center, circle farthest-corner, red, blue)",
+    "-moz-linear-gradient(left, red 0, blue 10px)",
+    "-o-repeating-linear-gradient(45deg, red, blue 10%)",
+    "conic-gradient(red 0, 50%, blue .25turn)",
+  ] {
+    with_gradient(value, |gradient| {
+      assert_eq!(gradient.unwrap().to_string(), value)
+    });
+  }
+}
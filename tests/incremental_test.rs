@@ -0,0 +1,132 @@
+use postcss_value_parser::incremental::{retokenize, TextEdit};
+use postcss_value_parser::span::Span;
+use postcss_value_parser::tokenizer::TokenType::*;
+use postcss_value_parser::tokenizer::{
+  Dialect, PlaceholderPattern, Token, Tokenizer, TokenizerOptions,
+};
+use std::ops::Range;
+
+fn tokenize<'a>(value: &'a str, options: TokenizerOptions) -> Vec<Token<'a>> {
+  let processor = Tokenizer::with_options(value, options);
+  let mut tokens = vec![];
+  while !processor.end_of_file() {
+    tokens.push(processor.next_token())
+  }
+  tokens
+}
+
+/// Apply every possible edit inserting one of `inserts` and compare against a full re-tokenization
+fn check_all_edits(value: &str, inserts: &[&str], options: TokenizerOptions) {
+  let old_tokens = tokenize(value, options);
+  for start in 0..=value.len() {
+    for end in start..=value.len() {
+      for insert in inserts {
+        let edit = TextEdit::new(Span::new(start, end), insert);
+        let new_value = edit.apply(value);
+        let result = retokenize(&old_tokens, &edit, &new_value, options);
+        assert_eq!(
+          result.tokens,
+          tokenize(&new_value, options),
+          "{:?} -> {:?}",
+          value,
+          new_value
+        );
+        for token in &result.tokens {
+          let source: &str = &new_value[Range::from(token.span)];
+          assert!(
+            source.contains(token.text),
+            "{:?} in {:?}",
+            token,
+            new_value
+          );
+        }
+        assert_eq!(
+          result.tokens.len() - result.changed.len(),
+          old_tokens.len() - result.replaced.len()
+        );
+      }
+    }
+  }
+}
+
+#[test]
+fn retokenizes_like_a_full_pass() {
+  let inserts = [
+    "", " ", "a", "\"", "'", "/*", "*/", "(", ",", "\\", "U+", "//",
+  ];
+  for value in [
+    "",
+    "1px solid red",
+    "a \"b c\" d",
+    "url( /* c */ x)",
+    "f(a, 'b')/2",
+    "U+0025-00FF, U+4??",
+    "\"unclosed",
+    "/* unclosed",
+    "a /*/",
+    "a\\",
+  ] {
+    check_all_edits(value, &inserts, Default::default());
+  }
+}
+
+#[test]
+fn retokenizes_dialects_and_placeholders_like_a_full_pass() {
+  let inserts = ["", " ", "}", "#{", "@{", "${", "//", "\n", ":", "__P", "_"];
+  let scss = TokenizerOptions {
+    dialect: Dialect::Scss,
+    ..Default::default()
+  };
+  let less = TokenizerOptions {
+    dialect: Dialect::Less,
+    ..Default::default()
+  };
+  let template = TokenizerOptions {
+    placeholder: Some(PlaceholderPattern::TemplateExpression),
+    ..Default::default()
+  };
+  let delimited = TokenizerOptions {
+    placeholder: Some(PlaceholderPattern::Delimited {
+      prefix: "__P",
+      suffix: "__",
+    }),
+    ..Default::default()
+  };
+  check_all_edits("a #{$b} // c\nhttp://d", &inserts, scss);
+  check_all_edits("@{a} @b // c", &inserts, less);
+  check_all_edits("${a}px ${b} c", &inserts, template);
+  check_all_edits("x__P1__y __P2 z", &inserts, delimited);
+}
+
+#[test]
+fn retokenizes_only_the_damaged_region() {
+  let value = "1px solid red, 2px dashed blue";
+  let old_tokens = tokenize(value, Default::default());
+  let edit = TextEdit::new(Span::new(4, 9), "dotted");
+  let new_value = edit.apply(value);
+  let result = retokenize(&old_tokens, &edit, &new_value, Default::default());
+  // the space before the edit is re-tokenized as it could have grown
+  assert_eq!(result.changed, 1..3);
+  assert_eq!(result.replaced, 1..3);
+  assert_eq!(result.tokens[2], Token::new(Word, "dotted", 4, 10));
+  assert_eq!(result.tokens[4], Token::new(Word, "red", 11, 14));
+}
+
+#[test]
+fn retokenizes_until_an_opened_string_is_closed() {
+  let value = "a b \" c";
+  let old_tokens = tokenize(value, Default::default());
+  let edit = TextEdit::new(Span::new(0, 0), "\"");
+  let new_value = edit.apply(value);
+  let result = retokenize(&old_tokens, &edit, &new_value, Default::default());
+  assert_eq!(
+    result.tokens,
+    vec![
+      Token::new(String, "\"a b \"", 0, 6),
+      Token::new(Space, " ", 6, 7),
+      Token::new(Word, "c", 7, 8),
+    ]
+  );
+  assert_eq!(result.changed, 0..3);
+  assert_eq!(result.replaced, 0..5);
+}
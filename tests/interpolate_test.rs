@@ -0,0 +1,185 @@
+use postcss_value_parser::interpolate::interpolate;
+use postcss_value_parser::node::stringify;
+use postcss_value_parser::parser::parse;
+
+fn at(from: &str, to: &str, progress: f64) -> String {
+  stringify(&interpolate(&parse(from), &parse(to), progress))
+}
+
+#[test]
+fn interpolates_numbers_and_dimensions() {
+  assert_eq!(at("0", "1", 0.25), ".25");
+  assert_eq!(at("10px", "20px", 0.5), "15px");
+  assert_eq!(at("1in", "48px", 0.5), ".75in");
+  assert_eq!(at("0", "100%", 0.3), "30%");
+  assert_eq!(at("10px 0", "20px 4em", 0.5), "15px 2em");
+  assert_eq!(at("10px", "50%", 0.5), "calc(5px + 25%)");
+  assert_eq!(at("calc(50% + 10px)", "0px", 0.5), "calc(25% + 5px)");
+  assert_eq!(at("0deg", "1turn", 0.5), "180deg");
+  // extrapolated by easings that overshoot
+  assert_eq!(at("0px", "10px", 1.5), "15px");
+  assert_eq!(at("1px solid", "3px solid", 0.5), "2px solid");
+  assert_eq!(
+    at("translate(1px) var(--x)", "translate(1px) var(--x)", 0.5),
+    "translate(1px) var(--x)"
+  );
+}
+
+#[test]
+fn interpolates_colors_in_oklab() {
+  assert_eq!(at("red", "red", 0.5), "red");
+  assert_eq!(at("#000", "#fff", 0.0), "rgb(0 0 0)");
+  assert_eq!(at("#000", "#fff", 1.0), "rgb(255 255 255)");
+  assert_eq!(
+    at("black", "white", 0.5),
+    "rgb(99.086079 99.086079 99.086079)"
+  );
+  assert_eq!(at("transparent", "red", 0.5), "rgb(255 0 0 / .5)");
+  assert_eq!(at("oklab(0 0 0)", "oklab(1 0 0)", 0.5), "oklab(.5 0 0)");
+  assert_eq!(at("1px solid red", "3px solid red", 0.5), "2px solid red");
+}
+
+#[test]
+fn interpolates_shadows() {
+  assert_eq!(
+    at("0 0 10px red", "10px 10px 20px blue", 0.5),
+    "5px 5px 15px rgb(140.362472 83.033272 162.307678)"
+  );
+  // the shorter list is padded with transparent shadows
+  assert_eq!(
+    at("none", "inset 2px 4px black", 0.5),
+    "inset 1px 2px rgb(0 0 0 / .5)"
+  );
+  assert_eq!(
+    at("1px 1px red", "3px 3px 2px red, 4px 4px blue", 0.5),
+    "2px 2px 1px rgb(255 0 0), 2px 2px rgb(0 0 255 / .5)"
+  );
+  // inset and outset shadows don't blend
+  assert_eq!(
+    at("inset 1px 1px red", "1px 1px red", 0.4),
+    "inset 1px 1px red"
+  );
+  assert_eq!(at("inset 1px 1px red", "1px 1px red", 0.6), "1px 1px red");
+}
+
+#[test]
+fn interpolates_transforms() {
+  assert_eq!(
+    at(
+      "translateX(0) rotate(0deg)",
+      "translate(100px, 50%) rotate(1turn)",
+      0.5
+    ),
+    "translate(50px, 25%) rotate(180deg)"
+  );
+  assert_eq!(
+    at("none", "scale(2) rotate(90deg)", 0.5),
+    "scale(1.5) rotate(45deg)"
+  );
+  assert_eq!(at("rotate(90deg)", "none", 0.5), "rotate(45deg)");
+  // the shorter list is padded with identity functions
+  assert_eq!(
+    at("translate(10px)", "translate(10px) rotate(1turn)", 0.5),
+    "translate(10px) rotate(180deg)"
+  );
+  assert_eq!(
+    at("translate(10px) rotate(0deg)", "translate(10px)", 0.5),
+    "translate(10px)"
+  );
+  assert_eq!(
+    at("translate(10px) scale(2)", "translate(20px)", 0.5),
+    "translate(15px) scale(1.5)"
+  );
+  assert_eq!(
+    at("translate(10px)", "translate3d(20px, 0, 10px)", 0.5),
+    "translate3d(15px, 0, 5px)"
+  );
+  // lists that don't match are blended as matrices
+  assert_eq!(
+    at("scale(2)", "rotate(90deg)", 0.0),
+    "matrix(2, 0, 0, 2, 0, 0)"
+  );
+  assert_eq!(
+    at("scale(2)", "rotate(90deg)", 1.0),
+    "matrix(0, 1, -1, 0, 0, 0)"
+  );
+  assert_eq!(
+    at("scale(1)", "rotate(90deg)", 0.5),
+    "matrix(.707107, .707107, -.707107, .707107, 0, 0)"
+  );
+  assert_eq!(
+    at(
+      "translate(10px) skewX(30deg)",
+      "translate(20px) scale(2)",
+      0.0
+    ),
+    "translate(10px) matrix(1, 0, .57735, 1, 0, 0)"
+  );
+  assert_eq!(at("none", "none", 0.5), "none");
+}
+
+#[test]
+fn falls_back_to_discrete() {
+  assert_eq!(at("block", "none", 0.4), "block");
+  assert_eq!(at("block", "none", 0.5), "none");
+  assert_eq!(at("10px", "10deg", 0.2), "10px");
+  assert_eq!(at("10px", "auto", 0.7), "auto");
+  assert_eq!(at("1px 2px", "1px", 0.2), "1px 2px");
+  assert_eq!(at("var(--a)", "10px", 0.2), "var(--a)");
+  // percentages can't be composed into a matrix
+  assert_eq!(at("translate(50%)", "rotate(90deg)", 0.7), "rotate(90deg)");
+  // a flattened 3D matrix can't be decomposed
+  assert_eq!(
+    at("rotateX(90deg)", "scale3d(1, 1, 0)", 0.2),
+    "rotateX(90deg)"
+  );
+}
+
+#[test]
+fn decomposes_matrices_without_loss() {
+  use postcss_value_parser::transform::TransformList;
+  let from = "rotate(30deg) skewX(20deg) scale(2, -1) translate(5px)";
+  let expected = TransformList::parse(&parse(from))
+    .unwrap()
+    .to_matrix()
+    .unwrap()
+    .to_string();
+  assert_eq!(at(from, "scale(3)", 0.0), expected);
+
+  let from = "translate3d(1px, 2px, 3px) perspective(100px) rotate3d(1, 2, 3, 40deg) skew(10deg, 20deg) scale3d(2, -1, .5)";
+  let expected = TransformList::parse(&parse(from))
+    .unwrap()
+    .to_matrix()
+    .unwrap()
+    .to_string();
+  assert_eq!(at(from, "rotateX(90deg)", 0.0), expected);
+}
+
+#[test]
+fn interpolates_3d_matrices() {
+  use postcss_value_parser::transform::TransformList;
+  let matrix = |value: &str| {
+    TransformList::parse(&parse(value))
+      .unwrap()
+      .to_matrix()
+      .unwrap()
+      .to_string()
+  };
+  assert_eq!(
+    at(
+      "translate(10px) rotateX(0deg)",
+      "translate(20px) scaleZ(2)",
+      0.5
+    ),
+    "translate(15px) matrix3d(1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1.5, 0, 0, 0, 0, 1)"
+  );
+  // rotations around different axes are blended along the shortest arc
+  assert_eq!(
+    at("rotateX(90deg)", "rotateY(90deg)", 0.5),
+    matrix("rotate3d(1, 1, 0, 70.528779deg)")
+  );
+  assert_eq!(
+    at("rotateX(90deg)", "rotateY(90deg)", 1.0),
+    matrix("rotateY(90deg)")
+  );
+}
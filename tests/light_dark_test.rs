@@ -0,0 +1,94 @@
+use postcss_value_parser::light_dark::{
+  contains_light_dark, lower, resolve, ColorScheme, LightDark,
+};
+use postcss_value_parser::node::{stringify, Node};
+use postcss_value_parser::parser::parse;
+
+fn resolved(value: &str, scheme: ColorScheme) -> String {
+  let mut nodes = parse(value);
+  resolve(&mut nodes, scheme);
+  stringify(&nodes)
+}
+
+#[test]
+fn parses_arguments() {
+  let nodes = parse("light-dark( #fff , rgb(0 0 0 / .5) )");
+  let function = match &nodes[0] {
+    Node::Function(function) => function,
+    node => panic!("{:?}", node),
+  };
+  let light_dark = LightDark::parse(function).unwrap();
+  assert_eq!(light_dark.light.to_string(), "#fff");
+  assert_eq!(light_dark.dark.to_string(), "rgb(0 0 0 / .5)");
+  assert_eq!(
+    light_dark.get(ColorScheme::Dark).to_string(),
+    "rgb(0 0 0 / .5)"
+  );
+  for valid in [
+    "light-dark(Canvas, CanvasText)",
+    "light-dark(var(--light), rgb(var(--rgb) / .5))",
+    "light-dark(currentcolor, light-dark(red, blue))",
+  ] {
+    match &parse(valid)[0] {
+      Node::Function(function) => assert!(LightDark::parse(function).is_some(), "{}", valid),
+      node => panic!("{:?}", node),
+    }
+  }
+
+  for invalid in [
+    "light-dark(red)",
+    "light-dark(red,)",
+    "light-dark(red, blue, green)",
+    "light-dark(red, blue",
+    "light-dark(1px, foo)",
+    "light-dark(red blue, green)",
+    "rgb(red, blue)",
+  ] {
+    match &parse(invalid)[0] {
+      Node::Function(function) => assert_eq!(LightDark::parse(function), None, "{}", invalid),
+      node => panic!("{:?}", node),
+    }
+  }
+}
+
+#[test]
+fn resolves_for_a_scheme() {
+  let value = "1px solid LIGHT-DARK(#333, #ccc)";
+  assert_eq!(resolved(value, ColorScheme::Light), "1px solid #333");
+  assert_eq!(resolved(value, ColorScheme::Dark), "1px solid #ccc");
+  assert_eq!(
+    resolved(
+      "linear-gradient(light-dark(white, black), light-dark(light-dark(red, blue), green))",
+      ColorScheme::Light
+    ),
+    "linear-gradient(white, red)"
+  );
+  assert_eq!(
+    resolved("var(--x, light-dark(red, blue))", ColorScheme::Dark),
+    "var(--x, blue)"
+  );
+  assert_eq!(
+    resolved("light-dark(red)", ColorScheme::Dark),
+    "light-dark(red)"
+  );
+  assert_eq!(
+    resolved("light-dark(1px, foo)", ColorScheme::Dark),
+    "light-dark(1px, foo)"
+  );
+}
+
+#[test]
+fn lowers_into_two_values() {
+  let nodes = parse("0 0 2px light-dark(rgb(0 0 0 / .2), rgb(255 255 255 / .2))");
+  let (light, dark) = lower(&nodes).unwrap();
+  assert_eq!(stringify(&light), "0 0 2px rgb(0 0 0 / .2)");
+  assert_eq!(stringify(&dark), "0 0 2px rgb(255 255 255 / .2)");
+
+  let nodes = parse("1px light-dark(1px, 2px)");
+  assert!(!contains_light_dark(&nodes));
+  assert_eq!(lower(&nodes), None);
+
+  let nodes = parse("red calc(1px + 2px)");
+  assert!(!contains_light_dark(&nodes));
+  assert_eq!(lower(&nodes), None);
+}
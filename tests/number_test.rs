@@ -0,0 +1,79 @@
+use postcss_value_parser::number::{format_dimension, format_number, FormatOptions};
+use postcss_value_parser::unit::{unit, Dimension};
+
+#[test]
+fn test_format_number() {
+  let options = FormatOptions::default();
+  let tests = vec![
+    (0.5, ".5"),
+    (-0.5, "-.5"),
+    (-0.0, "0"),
+    (0.0, "0"),
+    (10.0, "10"),
+    (100.0, "100"),
+    (1000.0, "1e3"),
+    (1000000.0, "1e6"),
+    (1500000.0, "15e5"),
+    (123.456, "123.456"),
+    (0.001, ".001"),
+    (0.0001, "1e-4"),
+    (0.00015, "15e-5"),
+    (-12.5, "-12.5"),
+    (1.0 / 3.0, ".3333333333333333"),
+    (f64::INFINITY, "calc(infinity)"),
+    (f64::NEG_INFINITY, "calc(-infinity)"),
+  ];
+
+  for (value, expected) in tests {
+    assert_eq!(format_number(value, &options), expected, "{}", value);
+  }
+  assert_eq!(format_number(f64::NAN, &options), "calc(NaN)");
+}
+
+#[test]
+fn test_format_number_with_precision() {
+  let options = FormatOptions { precision: Some(2) };
+  assert_eq!(format_number(1.0 / 3.0, &options), ".33");
+  assert_eq!(format_number(0.125, &options), ".13");
+  assert_eq!(format_number(-0.001, &options), "0");
+  assert_eq!(format_number(1e6, &options), "1e6");
+  assert_eq!(format_number(1e300, &options), "1e300");
+}
+
+#[test]
+fn test_format_dimension() {
+  let options = FormatOptions::default();
+  let tests = vec![
+    ("0.50", ".5"),
+    ("+0.50px", ".5px"),
+    ("-0.0", "0"),
+    ("-0.0em", "0em"),
+    ("10.000px", "10px"),
+    ("1000em", "1e3em"),
+    ("1e3em", "1e3em"),
+    ("1000000", "1e6"),
+    ("050%", "50%"),
+    ("1.50E+1s", "15s"),
+    ("1e999px", "1e999px"),
+  ];
+
+  for (fixture, expected) in tests {
+    let dimension = unit(fixture).unwrap();
+    assert_eq!(
+      format_dimension(&dimension, &options),
+      expected,
+      "{}",
+      fixture
+    );
+  }
+
+  // a unit that would be read as an exponent or fraction is escaped
+  assert_eq!(
+    format_dimension(&Dimension::new("10.0", "e3x"), &options),
+    "10\\65 3x"
+  );
+  assert_eq!(
+    format_dimension(&Dimension::new("2.0", ".5px"), &options),
+    "2\\2e 5px"
+  );
+}
@@ -0,0 +1,279 @@
+use postcss_value_parser::node::{
+  stringify, Comment, Div, Function, Node, Placeholder, Space, String, Word,
+};
+use postcss_value_parser::parser::{parse, parse_with_options};
+use postcss_value_parser::span::Span;
+use postcss_value_parser::tokenizer::{Dialect, PlaceholderPattern, TokenizerOptions};
+
+fn word(value: &str, start: usize, end: usize) -> Node<'_> {
+  Node::Word(Word {
+    span: Span::new(start, end),
+    value: value.into(),
+  })
+}
+
+fn space(value: &str, start: usize, end: usize) -> Node<'_> {
+  Node::Space(Space {
+    span: Span::new(start, end),
+    value: value.into(),
+  })
+}
+
+fn div(value: &str, start: usize, end: usize) -> Node<'_> {
+  Node::Div(Div {
+    span: Span::new(start, end),
+    value: value.into(),
+  })
+}
+
+/// Every value has to survive a round trip through the parser
+fn round_trip(value: &str) {
+  assert_eq!(stringify(&parse(value)), value);
+}
+
+#[test]
+fn parses_words_and_dividers() {
+  assert_eq!(
+    parse("1px solid, a/b"),
+    vec![
+      word("1px", 0, 3),
+      space(" ", 3, 4),
+      word("solid", 4, 9),
+      div(",", 9, 10),
+      space(" ", 10, 11),
+      word("a", 11, 12),
+      div("/", 12, 13),
+      word("b", 13, 14),
+    ]
+  );
+}
+
+#[test]
+fn parses_functions() {
+  assert_eq!(
+    parse("rgba(0, 0, 0)"),
+    vec![Node::Function(Function {
+      span: Span::new(0, 13),
+      value: "rgba".into(),
+      nodes: vec![
+        word("0", 5, 6),
+        div(",", 6, 7),
+        space(" ", 7, 8),
+        word("0", 8, 9),
+        div(",", 9, 10),
+        space(" ", 10, 11),
+        word("0", 11, 12),
+      ],
+      unclosed: false,
+    })]
+  );
+}
+
+#[test]
+fn parses_nested_and_unclosed_functions() {
+  assert_eq!(
+    parse("calc((1px"),
+    vec![Node::Function(Function {
+      span: Span::new(0, 9),
+      value: "calc".into(),
+      nodes: vec![Node::Function(Function {
+        span: Span::new(5, 9),
+        value: "".into(),
+        nodes: vec![word("1px", 6, 9)],
+        unclosed: true,
+      })],
+      unclosed: true,
+    })]
+  );
+  assert_eq!(parse("a)"), vec![word("a", 0, 1), word(")", 1, 2)]);
+}
+
+#[test]
+fn parses_strings_and_comments() {
+  assert_eq!(
+    parse("'a\\'b' \"c/* x */"),
+    vec![
+      Node::String(String {
+        span: Span::new(0, 6),
+        value: "a\\'b".into(),
+        quote: '\'',
+        unclosed: false,
+      }),
+      space(" ", 6, 7),
+      Node::String(String {
+        span: Span::new(7, 16),
+        value: "c/* x */".into(),
+        quote: '"',
+        unclosed: true,
+      }),
+    ]
+  );
+  assert_eq!(
+    parse("/* a */b/*"),
+    vec![
+      Node::Comment(Comment {
+        span: Span::new(0, 7),
+        value: " a ".into(),
+        inline: false,
+        unclosed: false,
+      }),
+      word("b", 7, 8),
+      Node::Comment(Comment {
+        span: Span::new(8, 10),
+        value: "".into(),
+        inline: false,
+        unclosed: true,
+      }),
+    ]
+  );
+}
+
+#[test]
+fn parses_unquoted_urls_as_a_single_word() {
+  assert_eq!(
+    parse("url( http://a.b/c?d=(e) )"),
+    vec![
+      Node::Function(Function {
+        span: Span::new(0, 23),
+        value: "url".into(),
+        nodes: vec![space(" ", 4, 5), word("http://a.b/c?d=(e", 5, 22)],
+        unclosed: false,
+      }),
+      space(" ", 23, 24),
+      word(")", 24, 25),
+    ]
+  );
+  assert_eq!(
+    parse("url('a b')"),
+    vec![Node::Function(Function {
+      span: Span::new(0, 10),
+      value: "url".into(),
+      nodes: vec![Node::String(String {
+        span: Span::new(4, 9),
+        value: "a b".into(),
+        quote: '\'',
+        unclosed: false,
+      })],
+      unclosed: false,
+    })]
+  );
+}
+
+#[test]
+fn parses_line_comments() {
+  let options = TokenizerOptions {
+    dialect: Dialect::Scss,
+    ..Default::default()
+  };
+  assert_eq!(
+    parse_with_options("a // b", options),
+    vec![
+      word("a", 0, 1),
+      space(" ", 1, 2),
+      Node::Comment(Comment {
+        span: Span::new(2, 6),
+        value: " b".into(),
+        inline: true,
+        unclosed: false,
+      }),
+    ]
+  );
+}
+
+#[test]
+fn parses_placeholders() {
+  let options = TokenizerOptions {
+    placeholder: Some(PlaceholderPattern::TemplateExpression),
+    ..Default::default()
+  };
+  assert_eq!(
+    parse_with_options("${a}px calc(${b})", options),
+    vec![
+      Node::Placeholder(Placeholder {
+        span: Span::new(0, 4),
+        value: "${a}".into(),
+      }),
+      word("px", 4, 6),
+      space(" ", 6, 7),
+      Node::Function(Function {
+        span: Span::new(7, 17),
+        value: "calc".into(),
+        nodes: vec![Node::Placeholder(Placeholder {
+          span: Span::new(12, 16),
+          value: "${b}".into(),
+        })],
+        unclosed: false,
+      }),
+    ]
+  );
+}
+
+#[test]
+fn stringifies_placeholders_as_they_were_written() {
+  let template = TokenizerOptions {
+    placeholder: Some(PlaceholderPattern::TemplateExpression),
+    ..Default::default()
+  };
+  let delimited = TokenizerOptions {
+    placeholder: Some(PlaceholderPattern::Delimited {
+      prefix: "__PLACEHOLDER_",
+      suffix: "__",
+    }),
+    ..Default::default()
+  };
+  for (value, options) in [
+    (
+      "${p => p.size}px solid ${({ theme }) => theme.color}",
+      template,
+    ),
+    ("translate(${x}, 0) 'a ${b}'", template),
+    (
+      "__PLACEHOLDER_3__ calc(__PLACEHOLDER_12__ + 1px)",
+      delimited,
+    ),
+  ] {
+    assert_eq!(stringify(&parse_with_options(value, options)), value);
+  }
+}
+
+#[test]
+fn parses_placeholders_with_a_pattern_read_at_runtime() {
+  let prefix = format!("__{}_", "SLOT");
+  let nodes = {
+    let options = TokenizerOptions {
+      placeholder: Some(PlaceholderPattern::Delimited {
+        prefix: &prefix,
+        suffix: "__",
+      }),
+      ..Default::default()
+    };
+    parse_with_options("__SLOT_0__px", options)
+  };
+  assert_eq!(
+    nodes,
+    vec![
+      Node::Placeholder(Placeholder {
+        span: Span::new(0, 10),
+        value: "__SLOT_0__".into(),
+      }),
+      word("px", 10, 12),
+    ]
+  );
+}
+
+#[test]
+fn stringifies_what_was_parsed() {
+  for value in [
+    "",
+    "1px solid rgba(0, 0, 0, .5)",
+    "calc( (1px + 2px) * 3 )",
+    "url( a b ) url('c') url(",
+    "\"a\\\"\" 'b",
+    "/* a */ b /* c",
+    "f(g(h(",
+    "U+0025-00FF, a:b",
+    "a)b",
+  ] {
+    round_trip(value);
+  }
+}
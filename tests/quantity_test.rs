@@ -0,0 +1,135 @@
+use postcss_value_parser::quantity::{ArithmeticError, Quantity, Sum};
+use postcss_value_parser::unit::{unit, UnitKind};
+
+fn q(value: &str) -> Quantity {
+  Quantity::from_dimension(&unit(value).unwrap())
+}
+
+#[test]
+fn test_add_and_sub() {
+  assert_eq!(q("1px") + q("2px"), Ok(q("3px")));
+  assert_eq!(q("1in") + q("96px"), Ok(q("2in")));
+  assert_eq!(q("10px") - q("2px"), Ok(q("8px")));
+  assert_eq!(q("1s") - q("500ms"), Ok(q(".5s")));
+  assert_eq!(q("10%") + q("5%"), Ok(q("15%")));
+  assert_eq!(q("1") + q("2"), Ok(q("3")));
+  assert_eq!(q("1EM") + q("1em"), Ok(q("2EM")));
+  assert_eq!(
+    (q("1turn") + q("180deg")).unwrap().to("deg"),
+    Some(q("540deg"))
+  );
+}
+
+#[test]
+fn test_incompatible_units() {
+  assert_eq!(
+    q("1px") + q("1s"),
+    Err(ArithmeticError::IncompatibleUnits {
+      left: "px".into(),
+      right: "s".into()
+    })
+  );
+  assert!(matches!(
+    q("1") + q("1px"),
+    Err(ArithmeticError::IncompatibleUnits { .. })
+  ));
+  assert!(matches!(
+    q("1px") * q("1px"),
+    Err(ArithmeticError::IncompatibleUnits { .. })
+  ));
+  assert!(matches!(
+    q("1") / q("1px"),
+    Err(ArithmeticError::IncompatibleUnits { .. })
+  ));
+  assert!(matches!(
+    q("1fr") + q("1px"),
+    Err(ArithmeticError::IncompatibleUnits { .. })
+  ));
+}
+
+#[test]
+fn test_unresolved_units() {
+  assert_eq!(
+    q("100%") - q("10px"),
+    Err(ArithmeticError::Unresolved {
+      left: "%".into(),
+      right: "px".into()
+    })
+  );
+  assert!(matches!(
+    q("1em") + q("1px"),
+    Err(ArithmeticError::Unresolved { .. })
+  ));
+  assert!(matches!(
+    q("1vw") - q("1rem"),
+    Err(ArithmeticError::Unresolved { .. })
+  ));
+}
+
+#[test]
+fn test_mul_and_div() {
+  assert_eq!(q("2") * q("5px"), Ok(q("10px")));
+  assert_eq!(q("5px") * q("2"), Ok(q("10px")));
+  assert_eq!(q("10px") / q("4"), Ok(q("2.5px")));
+  assert_eq!(q("1in") / q("48px"), Ok(q("2")));
+  assert_eq!((q("1px") / q("0")).unwrap().value, f64::INFINITY);
+  assert_eq!(q("50%") * q("2"), Ok(q("100%")));
+  assert_eq!(q("50%").kind(), UnitKind::Percentage);
+}
+
+#[test]
+fn test_display() {
+  assert_eq!((q("0.5px") + q("0.25px")).unwrap().to_string(), ".75px");
+  assert_eq!((-q("10px")).to_string(), "-10px");
+  assert_eq!(Quantity::number(1e6).to_string(), "1e6");
+}
+
+#[test]
+fn test_symbolic_sums() {
+  let sum = (Sum::from(q("50%")) + q("10px")).unwrap();
+  assert_eq!(sum.terms(), &[q("50%"), q("10px")]);
+  assert_eq!(sum.as_quantity(), None);
+  assert_eq!(sum.to_string(), "calc(50% + 10px)");
+
+  let sum = ((sum - q("1in")).unwrap() + q("25%")).unwrap();
+  assert_eq!(sum.to_string(), "calc(75% - 86px)");
+  assert_eq!(
+    (sum.clone() * q("2")).unwrap().to_string(),
+    "calc(150% - 172px)"
+  );
+  assert_eq!(
+    (sum.clone() / q("-2")).unwrap().to_string(),
+    "calc(-37.5% + 43px)"
+  );
+  assert_eq!((-sum.clone()).to_string(), "calc(-75% + 86px)");
+
+  let sum = (sum - (Sum::from(q("75%")) + q("1em")).unwrap()).unwrap();
+  assert_eq!(sum.to_string(), "calc(0% - 86px - 1em)");
+  let sum = (Sum::from(q("1em")) + q("2em")).unwrap();
+  assert_eq!(sum.as_quantity(), Some(&q("3em")));
+  assert_eq!(sum.to_string(), "3em");
+}
+
+#[test]
+fn test_invalid_symbolic_sums() {
+  let sum = (Sum::from(q("50%")) + q("10px")).unwrap();
+  assert_eq!(
+    sum.clone() + q("1s"),
+    Err(ArithmeticError::IncompatibleUnits {
+      left: "px".into(),
+      right: "s".into()
+    })
+  );
+  assert!(matches!(
+    sum.clone() * q("1px"),
+    Err(ArithmeticError::IncompatibleUnits { .. })
+  ));
+  assert!(matches!(
+    sum / q("1px"),
+    Err(ArithmeticError::Unresolved { .. })
+  ));
+  assert_eq!(
+    (Sum::from(q("2")) * q("5px")).map(|sum| sum.to_string()),
+    Ok("10px".to_string())
+  );
+}
@@ -0,0 +1,100 @@
+use postcss_value_parser::node::stringify;
+use postcss_value_parser::parser::parse;
+use postcss_value_parser::resolve::ResolutionContext;
+use postcss_value_parser::unit::unit;
+
+fn context() -> ResolutionContext {
+  ResolutionContext {
+    root_font_size: 10.0,
+    font_size: 20.0,
+    viewport_width: Some(1000.0),
+    viewport_height: Some(500.0),
+    ..Default::default()
+  }
+}
+
+fn resolve(value: &str, context: &ResolutionContext) -> String {
+  let mut nodes = parse(value);
+  context.resolve_nodes(&mut nodes);
+  stringify(&nodes)
+}
+
+#[test]
+fn resolves_dimensions() {
+  let context = context();
+  let tests = [
+    ("2em", Some(40.0)),
+    ("2rem", Some(20.0)),
+    ("1ex", Some(10.0)),
+    ("1ch", Some(10.0)),
+    ("1rch", Some(5.0)),
+    ("1lh", Some(24.0)),
+    ("10vw", Some(100.0)),
+    ("10VH", Some(50.0)),
+    ("10vmin", Some(50.0)),
+    ("10dvmax", Some(100.0)),
+    ("10svi", Some(100.0)),
+    ("10cqw", Some(100.0)),
+    ("1in", Some(96.0)),
+    ("1px", Some(1.0)),
+    ("50%", None),
+    ("1cap", Some(14.0)),
+    ("10rcap", Some(70.0)),
+    ("1deg", None),
+    ("1", None),
+  ];
+  for (value, expected) in tests {
+    assert_eq!(
+      context.resolve(&unit(value).unwrap()),
+      expected,
+      "{}",
+      value
+    );
+  }
+}
+
+#[test]
+fn uses_known_metrics_and_containers() {
+  let context = ResolutionContext {
+    x_height: Some(9.0),
+    cap_height: Some(15.0),
+    container_width: Some(200.0),
+    container_height: Some(300.0),
+    percentage_basis: Some(400.0),
+    ..context()
+  };
+  assert_eq!(context.resolve_value(1.0, "ex"), Some(9.0));
+  // the root font has its own metrics
+  assert_eq!(context.resolve_value(1.0, "rex"), Some(5.0));
+  assert_eq!(context.resolve_value(2.0, "cap"), Some(30.0));
+  assert_eq!(context.resolve_value(1.0, "rcap"), Some(7.0));
+  assert_eq!(context.resolve_value(10.0, "cqi"), Some(20.0));
+  assert_eq!(context.resolve_value(10.0, "cqmax"), Some(30.0));
+  assert_eq!(context.resolve_value(50.0, "%"), Some(200.0));
+  assert_eq!(ResolutionContext::default().resolve_value(1.0, "vw"), None);
+}
+
+#[test]
+fn resolves_node_trees() {
+  let context = ResolutionContext {
+    percentage_basis: Some(200.0),
+    ..context()
+  };
+  assert_eq!(
+    resolve("1rem 2em/1.5 10vw 50%", &context),
+    "10px 40px/1.5 100px 100px"
+  );
+  assert_eq!(
+    resolve("calc(1em + 10%) translate(1rem, 2px)", &context),
+    "calc(20px + 20px) translate(10px, 2px)"
+  );
+  // percentages mean something else in most functions
+  assert_eq!(
+    resolve("rgb(10% 20% 30%) url(1em) 1deg", &context),
+    "rgb(10% 20% 30%) url(1em) 1deg"
+  );
+  assert_eq!(
+    resolve("1rem 50%", &ResolutionContext::default()),
+    "16px 50%"
+  );
+}
@@ -0,0 +1,27 @@
+use postcss_value_parser::span::Span;
+use postcss_value_parser::tokenizer::{TokenType, Tokenizer};
+
+#[test]
+fn test_span() {
+  let span = Span::new(2, 5);
+  assert_eq!(span.len(), 3);
+  assert!(!span.is_empty());
+  assert!(Span::new(4, 4).is_empty());
+  assert!(span.contains(2));
+  assert!(span.contains(4));
+  assert!(!span.contains(5));
+  assert_eq!(span.merge(Span::new(7, 9)), Span::new(2, 9));
+  assert_eq!(Span::new(7, 9).merge(span), Span::new(2, 9));
+  assert_eq!(Span::from(2..5), span);
+  assert_eq!(std::ops::Range::from(span), 2..5);
+}
+
+#[test]
+fn test_token_span() {
+  let value = "10px solid";
+  let processor = Tokenizer::new(value);
+  let token = processor.next_token();
+  assert_eq!(token.kind, TokenType::Word);
+  assert_eq!(token.text, "10px");
+  assert_eq!(&value[std::ops::Range::from(token.span)], token.text);
+}
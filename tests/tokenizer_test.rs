@@ -1,7 +1,9 @@
 use postcss_value_parser::tokenizer::TokenType::*;
-use postcss_value_parser::tokenizer::{Token, Tokenizer};
+use postcss_value_parser::tokenizer::{
+  Dialect, PlaceholderPattern, Token, Tokenizer, TokenizerOptions,
+};
 
-fn tokenize(value: &str) -> Vec<Token> {
+fn tokenize(value: &str) -> Vec<Token<'_>> {
   let processor = Tokenizer::new(value);
   let mut tokens = vec![];
   while !processor.end_of_file() {
@@ -14,6 +16,23 @@ fn run(value: &str, tokens: Vec<Token>) {
   assert_eq!(tokenize(value), tokens);
 }
 
+fn run_dialect(dialect: Dialect, value: &str, tokens: Vec<Token>) {
+  let options = TokenizerOptions {
+    dialect,
+    ..Default::default()
+  };
+  run_with_options(options, value, tokens);
+}
+
+fn run_with_options(options: TokenizerOptions, value: &str, tokens: Vec<Token>) {
+  let processor = Tokenizer::with_options(value, options);
+  let mut result = vec![];
+  while !processor.end_of_file() {
+    result.push(processor.next_token())
+  }
+  assert_eq!(result, tokens);
+}
+
 #[test]
 fn tokenizes_empty_file() {
   run("", vec![]);
@@ -21,27 +40,30 @@ fn tokenizes_empty_file() {
 
 #[test]
 fn tokenizes_space() {
-  run("\r\n \u{c}\t", vec![Token(Space, "\r\n \u{c}\t", 0, 5)]);
+  run(
+    "\r\n \u{c}\t",
+    vec![Token::new(Space, "\r\n \u{c}\t", 0, 5)],
+  );
 }
 
 #[test]
 fn tokenizes_word() {
-  run("ab", vec![Token(Word, "ab", 0, 2)]);
+  run("ab", vec![Token::new(Word, "ab", 0, 2)]);
 }
 
 #[test]
 fn tokenizes_should_process_escaped_parentheses_open() {
-  run("\\(", vec![Token(Word, "\\(", 0, 2)]);
+  run("\\(", vec![Token::new(Word, "\\(", 0, 2)]);
 }
 
 #[test]
 fn tokenizes_should_process_escaped_parentheses_close() {
-  run("\\)", vec![Token(Word, "\\)", 0, 2)]);
+  run("\\)", vec![Token::new(Word, "\\)", 0, 2)]);
 }
 
 #[test]
 fn tokenizes_should_process_escaped_parentheses_both() {
-  run("\\(\\)", vec![Token(Word, "\\(\\)", 0, 4)]);
+  run("\\(\\)", vec![Token::new(Word, "\\(\\)", 0, 4)]);
 }
 
 #[test]
@@ -49,9 +71,9 @@ fn tokenizes_should_process_escaped_parentheses_both_with_space() {
   run(
     "\\( \\)",
     vec![
-      Token(Word, "\\(", 0, 2),
-      Token(Space, " ", 2, 3),
-      Token(Word, "\\)", 3, 5),
+      Token::new(Word, "\\(", 0, 2),
+      Token::new(Space, " ", 2, 3),
+      Token::new(Word, "\\)", 3, 5),
     ],
   );
 }
@@ -61,9 +83,9 @@ fn tokenizes_should_process_space_in_parentheses() {
   run(
     "( )",
     vec![
-      Token(OpenParentheses, "(", 0, 1),
-      Token(Space, " ", 1, 2),
-      Token(CloseParentheses, ")", 2, 3),
+      Token::new(OpenParentheses, "(", 0, 1),
+      Token::new(Space, " ", 1, 2),
+      Token::new(CloseParentheses, ")", 2, 3),
     ],
   );
 }
@@ -73,11 +95,11 @@ fn tokenizes_should_process_word_in_parentheses() {
   run(
     "( | )",
     vec![
-      Token(OpenParentheses, "(", 0, 1),
-      Token(Space, " ", 1, 2),
-      Token(Word, "|", 2, 3),
-      Token(Space, " ", 3, 4),
-      Token(CloseParentheses, ")", 4, 5),
+      Token::new(OpenParentheses, "(", 0, 1),
+      Token::new(Space, " ", 1, 2),
+      Token::new(Word, "|", 2, 3),
+      Token::new(Space, " ", 3, 4),
+      Token::new(CloseParentheses, ")", 4, 5),
     ],
   );
 }
@@ -87,29 +109,29 @@ fn tokenizes_should_process_nested_parentheses() {
   run(
     "((()))",
     vec![
-      Token(OpenParentheses, "(", 0, 1),
-      Token(OpenParentheses, "(", 1, 2),
-      Token(OpenParentheses, "(", 2, 3),
-      Token(CloseParentheses, ")", 3, 4),
-      Token(CloseParentheses, ")", 4, 5),
-      Token(CloseParentheses, ")", 5, 6),
+      Token::new(OpenParentheses, "(", 0, 1),
+      Token::new(OpenParentheses, "(", 1, 2),
+      Token::new(OpenParentheses, "(", 2, 3),
+      Token::new(CloseParentheses, ")", 3, 4),
+      Token::new(CloseParentheses, ")", 4, 5),
+      Token::new(CloseParentheses, ")", 5, 6),
     ],
   );
 }
 
 #[test]
 fn tokenizes_should_process_divider_slash() {
-  run("/", vec![Token(Div, "/", 0, 1)]);
+  run("/", vec![Token::new(Div, "/", 0, 1)]);
 }
 
 #[test]
 fn tokenizes_should_process_divider_colon() {
-  run(":", vec![Token(Div, ":", 0, 1)]);
+  run(":", vec![Token::new(Div, ":", 0, 1)]);
 }
 
 #[test]
 fn tokenizes_should_process_divider_comma() {
-  run(",", vec![Token(Div, ",", 0, 1)]);
+  run(",", vec![Token::new(Div, ",", 0, 1)]);
 }
 
 #[test]
@@ -117,9 +139,9 @@ fn tokenizes_should_process_complex_divider() {
   run(
     " , ",
     vec![
-      Token(Space, " ", 0, 1),
-      Token(Div, ",", 1, 2),
-      Token(Space, " ", 2, 3),
+      Token::new(Space, " ", 0, 1),
+      Token::new(Div, ",", 1, 2),
+      Token::new(Space, " ", 2, 3),
     ],
   );
 }
@@ -129,11 +151,11 @@ fn tokenizes_should_process_divider_in_parentheses() {
   run(
     "( , )",
     vec![
-      Token(OpenParentheses, "(", 0, 1),
-      Token(Space, " ", 1, 2),
-      Token(Div, ",", 2, 3),
-      Token(Space, " ", 3, 4),
-      Token(CloseParentheses, ")", 4, 5),
+      Token::new(OpenParentheses, "(", 0, 1),
+      Token::new(Space, " ", 1, 2),
+      Token::new(Div, ",", 2, 3),
+      Token::new(Space, " ", 3, 4),
+      Token::new(CloseParentheses, ")", 4, 5),
     ],
   );
 }
@@ -143,64 +165,79 @@ fn tokenizes_should_process_two_spaced_divider() {
   run(
     " , : ",
     vec![
-      Token(Space, " ", 0, 1),
-      Token(Div, ",", 1, 2),
-      Token(Space, " ", 2, 3),
-      Token(Div, ":", 3, 4),
-      Token(Space, " ", 4, 5),
+      Token::new(Space, " ", 0, 1),
+      Token::new(Div, ",", 1, 2),
+      Token::new(Space, " ", 2, 3),
+      Token::new(Div, ":", 3, 4),
+      Token::new(Space, " ", 4, 5),
     ],
   );
 }
 
 #[test]
 fn tokenizes_should_process_empty_double_quoted_strings() {
-  run("\"\"", vec![Token(String, "\"\"", 0, 2)]);
+  run("\"\"", vec![Token::new(String, "\"\"", 0, 2)]);
 }
 
 #[test]
 fn tokenizes_should_process_empty_double_quoted_strings_unclosed() {
-  run("\"", vec![Token(String, "\"", 0, 1)]);
+  run(
+    "\"",
+    vec![Token::new(String, "\"", 0, 1).with_unclosed(true)],
+  );
 }
 
 #[test]
 fn tokenizes_should_process_empty_single_quoted_strings() {
-  run("''", vec![Token(String, "''", 0, 2)]);
+  run("''", vec![Token::new(String, "''", 0, 2)]);
 }
 
 #[test]
 fn tokenizes_should_process_escaped_double_quotes() {
   run(
     "\"word\\\"word\"",
-    vec![Token(String, "\"word\\\"word\"", 0, 12)],
+    vec![Token::new(String, "\"word\\\"word\"", 0, 12)],
   );
 }
 
 #[test]
 fn tokenizes_should_process_escaped_single_quotes() {
-  run("'word\\'word'", vec![Token(String, "'word\\'word'", 0, 12)]);
+  run(
+    "'word\\'word'",
+    vec![Token::new(String, "'word\\'word'", 0, 12)],
+  );
 }
 
 #[test]
 fn tokenizes_should_process_single_quotes_inside_double_quotes() {
   run(
     "\"word'word\"",
-    vec![Token(String, "\"word\'word\"", 0, 11)],
+    vec![Token::new(String, "\"word\'word\"", 0, 11)],
   );
 }
 
 #[test]
 fn tokenizes_should_process_double_quotes_inside_single_quotes() {
-  run("'word\"word'", vec![Token(String, "'word\"word'", 0, 11)]);
+  run(
+    "'word\"word'",
+    vec![Token::new(String, "'word\"word'", 0, 11)],
+  );
 }
 
 #[test]
 fn tokenizes_should_process_unclosed_quotes() {
-  run("\"word", vec![Token(String, "\"word", 0, 5)]);
+  run(
+    "\"word",
+    vec![Token::new(String, "\"word", 0, 5).with_unclosed(true)],
+  );
 }
 
 #[test]
 fn tokenizes_should_process_unclosed_quotes_with_ended_backslash() {
-  run("\"word\\", vec![Token(String, "\"word\\", 0, 6)]);
+  run(
+    "\"word\\",
+    vec![Token::new(String, "\"word\\", 0, 6).with_unclosed(true)],
+  );
 }
 
 #[test]
@@ -208,9 +245,9 @@ fn tokenizes_should_process_quoted_strings_and_words() {
   run(
     "word1'string'word2",
     vec![
-      Token(Word, "word1", 0, 5),
-      Token(String, "'string'", 5, 13),
-      Token(Word, "word2", 13, 18),
+      Token::new(Word, "word1", 0, 5),
+      Token::new(String, "'string'", 5, 13),
+      Token::new(Word, "word2", 13, 18),
     ],
   );
 }
@@ -220,9 +257,9 @@ fn tokenizes_should_process_escaped_symbols_as_words() {
   run(
     " \\\"word\\'\\ \\\t ",
     vec![
-      Token(Space, " ", 0, 1),
-      Token(Word, "\\\"word\\'\\ \\\t", 1, 13),
-      Token(Space, " ", 13, 14),
+      Token::new(Space, " ", 0, 1),
+      Token::new(Word, "\\\"word\\'\\ \\\t", 1, 13),
+      Token::new(Space, " ", 13, 14),
     ],
   );
 }
@@ -232,25 +269,25 @@ fn tokenizes_should_process_font_value() {
   run(
     "bold italic 12px \t /3 'Open Sans', Arial, \"Helvetica Neue\", sans-serif",
     vec![
-      Token(Word, "bold", 0, 4),
-      Token(Space, " ", 4, 5),
-      Token(Word, "italic", 5, 11),
-      Token(Space, " ", 11, 12),
-      Token(Word, "12px", 12, 16),
-      Token(Space, " \t ", 16, 19),
-      Token(Div, "/", 19, 20),
-      Token(Word, "3", 20, 21),
-      Token(Space, " ", 21, 22),
-      Token(String, "'Open Sans'", 22, 33),
-      Token(Div, ",", 33, 34),
-      Token(Space, " ", 34, 35),
-      Token(Word, "Arial", 35, 40),
-      Token(Div, ",", 40, 41),
-      Token(Space, " ", 41, 42),
-      Token(String, "\"Helvetica Neue\"", 42, 58),
-      Token(Div, ",", 58, 59),
-      Token(Space, " ", 59, 60),
-      Token(Word, "sans-serif", 60, 70),
+      Token::new(Word, "bold", 0, 4),
+      Token::new(Space, " ", 4, 5),
+      Token::new(Word, "italic", 5, 11),
+      Token::new(Space, " ", 11, 12),
+      Token::new(Word, "12px", 12, 16),
+      Token::new(Space, " \t ", 16, 19),
+      Token::new(Div, "/", 19, 20),
+      Token::new(Word, "3", 20, 21),
+      Token::new(Space, " ", 21, 22),
+      Token::new(String, "'Open Sans'", 22, 33),
+      Token::new(Div, ",", 33, 34),
+      Token::new(Space, " ", 34, 35),
+      Token::new(Word, "Arial", 35, 40),
+      Token::new(Div, ",", 40, 41),
+      Token::new(Space, " ", 41, 42),
+      Token::new(String, "\"Helvetica Neue\"", 42, 58),
+      Token::new(Div, ",", 58, 59),
+      Token::new(Space, " ", 59, 60),
+      Token::new(Word, "sans-serif", 60, 70),
     ],
   );
 }
@@ -260,19 +297,19 @@ fn tokenizes_should_process_color_value() {
   run(
     "rgba( 29, 439 , 29 )",
     vec![
-      Token(Word, "rgba", 0, 4),
-      Token(OpenParentheses, "(", 4, 5),
-      Token(Space, " ", 5, 6),
-      Token(Word, "29", 6, 8),
-      Token(Div, ",", 8, 9),
-      Token(Space, " ", 9, 10),
-      Token(Word, "439", 10, 13),
-      Token(Space, " ", 13, 14),
-      Token(Div, ",", 14, 15),
-      Token(Space, " ", 15, 16),
-      Token(Word, "29", 16, 18),
-      Token(Space, " ", 18, 19),
-      Token(CloseParentheses, ")", 19, 20),
+      Token::new(Word, "rgba", 0, 4),
+      Token::new(OpenParentheses, "(", 4, 5),
+      Token::new(Space, " ", 5, 6),
+      Token::new(Word, "29", 6, 8),
+      Token::new(Div, ",", 8, 9),
+      Token::new(Space, " ", 9, 10),
+      Token::new(Word, "439", 10, 13),
+      Token::new(Space, " ", 13, 14),
+      Token::new(Div, ",", 14, 15),
+      Token::new(Space, " ", 15, 16),
+      Token::new(Word, "29", 16, 18),
+      Token::new(Space, " ", 18, 19),
+      Token::new(CloseParentheses, ")", 19, 20),
     ],
   );
 }
@@ -282,17 +319,17 @@ fn tokenizes_should_process_url_function() {
   run(
     "url( /gfx/img/bg.jpg )",
     vec![
-      Token(Word, "url", 0, 3),
-      Token(OpenParentheses, "(", 3, 4),
-      Token(Space, " ", 4, 5),
-      Token(Div, "/", 5, 6),
-      Token(Word, "gfx", 6, 9),
-      Token(Div, "/", 9, 10),
-      Token(Word, "img", 10, 13),
-      Token(Div, "/", 13, 14),
-      Token(Word, "bg.jpg", 14, 20),
-      Token(Space, " ", 20, 21),
-      Token(CloseParentheses, ")", 21, 22),
+      Token::new(Word, "url", 0, 3),
+      Token::new(OpenParentheses, "(", 3, 4),
+      Token::new(Space, " ", 4, 5),
+      Token::new(Div, "/", 5, 6),
+      Token::new(Word, "gfx", 6, 9),
+      Token::new(Div, "/", 9, 10),
+      Token::new(Word, "img", 10, 13),
+      Token::new(Div, "/", 13, 14),
+      Token::new(Word, "bg.jpg", 14, 20),
+      Token::new(Space, " ", 20, 21),
+      Token::new(CloseParentheses, ")", 21, 22),
     ],
   );
 }
@@ -302,14 +339,14 @@ fn tokenizes_should_process_url_function_with_quoted_first_argument() {
   run(
     "url( \"/gfx/img/bg.jpg\" hello )",
     vec![
-      Token(Word, "url", 0, 3),
-      Token(OpenParentheses, "(", 3, 4),
-      Token(Space, " ", 4, 5),
-      Token(String, "\"/gfx/img/bg.jpg\"", 5, 22),
-      Token(Space, " ", 22, 23),
-      Token(Word, "hello", 23, 28),
-      Token(Space, " ", 28, 29),
-      Token(CloseParentheses, ")", 29, 30),
+      Token::new(Word, "url", 0, 3),
+      Token::new(OpenParentheses, "(", 3, 4),
+      Token::new(Space, " ", 4, 5),
+      Token::new(String, "\"/gfx/img/bg.jpg\"", 5, 22),
+      Token::new(Space, " ", 22, 23),
+      Token::new(Word, "hello", 23, 28),
+      Token::new(Space, " ", 28, 29),
+      Token::new(CloseParentheses, ")", 29, 30),
     ],
   );
 }
@@ -319,14 +356,14 @@ fn tokenizes_should_process_division_with_spaces() {
   run(
     "calc(1 / 2)",
     vec![
-      Token(Word, "calc", 0, 4),
-      Token(OpenParentheses, "(", 4, 5),
-      Token(Word, "1", 5, 6),
-      Token(Space, " ", 6, 7),
-      Token(Div, "/", 7, 8),
-      Token(Space, " ", 8, 9),
-      Token(Word, "2", 9, 10),
-      Token(CloseParentheses, ")", 10, 11),
+      Token::new(Word, "calc", 0, 4),
+      Token::new(OpenParentheses, "(", 4, 5),
+      Token::new(Word, "1", 5, 6),
+      Token::new(Space, " ", 6, 7),
+      Token::new(Div, "/", 7, 8),
+      Token::new(Space, " ", 8, 9),
+      Token::new(Word, "2", 9, 10),
+      Token::new(CloseParentheses, ")", 10, 11),
     ],
   );
 }
@@ -336,12 +373,12 @@ fn tokenizes_should_process_multiplication_without_spaces() {
   run(
     "calc(1*2)",
     vec![
-      Token(Word, "calc", 0, 4),
-      Token(OpenParentheses, "(", 4, 5),
-      Token(Word, "1", 5, 6),
-      Token(Word, "*", 6, 7),
-      Token(Word, "2", 7, 8),
-      Token(CloseParentheses, ")", 8, 9),
+      Token::new(Word, "calc", 0, 4),
+      Token::new(OpenParentheses, "(", 4, 5),
+      Token::new(Word, "1", 5, 6),
+      Token::new(Word, "*", 6, 7),
+      Token::new(Word, "2", 7, 8),
+      Token::new(CloseParentheses, ")", 8, 9),
     ],
   );
 }
@@ -351,15 +388,15 @@ fn tokenizes_should_process_comments() {
   run(
     "/*before*/ 1px /*between*/ 1px /*after*/",
     vec![
-      Token(Comment, "before", 0, 9),
-      Token(Space, " ", 10, 11),
-      Token(Word, "1px", 11, 14),
-      Token(Space, " ", 14, 15),
-      Token(Comment, "between", 15, 25),
-      Token(Space, " ", 26, 27),
-      Token(Word, "1px", 27, 30),
-      Token(Space, " ", 30, 31),
-      Token(Comment, "after", 31, 39),
+      Token::new(Comment, "before", 0, 10),
+      Token::new(Space, " ", 10, 11),
+      Token::new(Word, "1px", 11, 14),
+      Token::new(Space, " ", 14, 15),
+      Token::new(Comment, "between", 15, 26),
+      Token::new(Space, " ", 26, 27),
+      Token::new(Word, "1px", 27, 30),
+      Token::new(Space, " ", 30, 31),
+      Token::new(Comment, "after", 31, 40),
     ],
   );
 }
@@ -369,21 +406,21 @@ fn tokenizes_should_process_comments_inside_functions() {
   run(
     "rgba( 0, 55/55, 0/*,.5*/ )",
     vec![
-      Token(Word, "rgba", 0, 4),
-      Token(OpenParentheses, "(", 4, 5),
-      Token(Space, " ", 5, 6),
-      Token(Word, "0", 6, 7),
-      Token(Div, ",", 7, 8),
-      Token(Space, " ", 8, 9),
-      Token(Word, "55", 9, 11),
-      Token(Div, "/", 11, 12),
-      Token(Word, "55", 12, 14),
-      Token(Div, ",", 14, 15),
-      Token(Space, " ", 15, 16),
-      Token(Word, "0", 16, 17),
-      Token(Comment, ",.5", 17, 23),
-      Token(Space, " ", 24, 25),
-      Token(CloseParentheses, ")", 25, 26),
+      Token::new(Word, "rgba", 0, 4),
+      Token::new(OpenParentheses, "(", 4, 5),
+      Token::new(Space, " ", 5, 6),
+      Token::new(Word, "0", 6, 7),
+      Token::new(Div, ",", 7, 8),
+      Token::new(Space, " ", 8, 9),
+      Token::new(Word, "55", 9, 11),
+      Token::new(Div, "/", 11, 12),
+      Token::new(Word, "55", 12, 14),
+      Token::new(Div, ",", 14, 15),
+      Token::new(Space, " ", 15, 16),
+      Token::new(Word, "0", 16, 17),
+      Token::new(Comment, ",.5", 17, 24),
+      Token::new(Space, " ", 24, 25),
+      Token::new(CloseParentheses, ")", 25, 26),
     ],
   );
 }
@@ -393,50 +430,70 @@ fn tokenizes_should_process_unclosed_comments() {
   run(
     "/*comment*/ 1px /* unclosed ",
     vec![
-      Token(Comment, "comment", 0, 10),
-      Token(Space, " ", 11, 12),
-      Token(Word, "1px", 12, 15),
-      Token(Space, " ", 15, 16),
-      Token(Comment, " unclosed ", 16, 29),
+      Token::new(Comment, "comment", 0, 11),
+      Token::new(Space, " ", 11, 12),
+      Token::new(Word, "1px", 12, 15),
+      Token::new(Space, " ", 15, 16),
+      Token::new(Comment, " unclosed ", 16, 28).with_unclosed(true),
     ],
   );
 }
 
+#[test]
+fn tokenizes_should_not_close_comments_with_the_opening_star() {
+  run(
+    "/*/",
+    vec![Token::new(Comment, "/", 0, 3).with_unclosed(true)],
+  );
+}
+
 #[test]
 fn tokenizes_should_process_escape_character() {
   run(
     "Hawaii \\35 -0",
     vec![
-      Token(Word, "Hawaii", 0, 6),
-      Token(Space, " ", 6, 7),
-      Token(Word, "\\35", 7, 10),
-      Token(Space, " ", 10, 11),
-      Token(Word, "-0", 11, 13),
+      Token::new(Word, "Hawaii", 0, 6),
+      Token::new(Space, " ", 6, 7),
+      Token::new(Word, "\\35", 7, 10),
+      Token::new(Space, " ", 10, 11),
+      Token::new(Word, "-0", 11, 13),
+    ],
+  );
+}
+
+#[test]
+fn tokenizes_should_end_trailing_escapes_at_the_end_of_input() {
+  run(
+    "a \\",
+    vec![
+      Token::new(Word, "a", 0, 1),
+      Token::new(Space, " ", 1, 2),
+      Token::new(Word, "\\", 2, 3),
     ],
   );
 }
 
 #[test]
 fn tokenizes_should_process_unicode_range_single_codepoint() {
-  run("U+26", vec![Token(UnicodeRange, "U+26", 0, 4)]);
+  run("U+26", vec![Token::new(UnicodeRange, "U+26", 0, 4)]);
 }
 
 #[test]
 fn tokenizes_should_process_unicode_range_single_codepoint2() {
-  run("U+0-7F", vec![Token(UnicodeRange, "U+0-7F", 0, 6)]);
+  run("U+0-7F", vec![Token::new(UnicodeRange, "U+0-7F", 0, 6)]);
 }
 
 #[test]
 fn tokenizes_should_process_unicode_range_codepoint_range() {
   run(
     "U+0025-00FF",
-    vec![Token(UnicodeRange, "U+0025-00FF", 0, 11)],
+    vec![Token::new(UnicodeRange, "U+0025-00FF", 0, 11)],
   );
 }
 
 #[test]
 fn tokenizes_should_process_unicode_range_wildcard_range() {
-  run("U+4??", vec![Token(UnicodeRange, "U+4??", 0, 5)]);
+  run("U+4??", vec![Token::new(UnicodeRange, "U+4??", 0, 5)]);
 }
 
 #[test]
@@ -444,25 +501,232 @@ fn tokenizes_should_process_unicode_range_multiple_values() {
   run(
     "U+0025-00FF, U+4??",
     vec![
-      Token(UnicodeRange, "U+0025-00FF", 0, 11),
-      Token(Div, ",", 11, 12),
-      Token(Space, " ", 12, 13),
-      Token(UnicodeRange, "U+4??", 13, 18),
+      Token::new(UnicodeRange, "U+0025-00FF", 0, 11),
+      Token::new(Div, ",", 11, 12),
+      Token::new(Space, " ", 12, 13),
+      Token::new(UnicodeRange, "U+4??", 13, 18),
     ],
   );
 }
 
 #[test]
 fn tokenizes_should_process_invalid_unicode_range_as_word() {
-  run("U+4??Z", vec![Token(Word, "U+4??Z", 0, 6)]);
+  run("U+4??Z", vec![Token::new(Word, "U+4??Z", 0, 6)]);
 }
 
 #[test]
 fn tokenizes_should_process_invalid_unicode_range_as_word2() {
-  run("U+", vec![Token(Word, "U+", 0, 2)]);
+  run("U+", vec![Token::new(Word, "U+", 0, 2)]);
 }
 
 #[test]
 fn tokenizes_should_process_invalid_unicode_range_as_word3() {
-  run("U+Z", vec![Token(Word, "U+Z", 0, 3)]);
+  run("U+Z", vec![Token::new(Word, "U+Z", 0, 3)]);
+}
+
+#[test]
+fn tokenizes_should_keep_double_slash_as_div_in_css() {
+  run(
+    "a//b",
+    vec![
+      Token::new(Word, "a", 0, 1),
+      Token::new(Div, "/", 1, 2),
+      Token::new(Div, "/", 2, 3),
+      Token::new(Word, "b", 3, 4),
+    ],
+  );
+}
+
+#[test]
+fn tokenizes_should_process_line_comments_in_scss() {
+  run_dialect(
+    Dialect::Scss,
+    "1px // comment\n2px",
+    vec![
+      Token::new(Word, "1px", 0, 3),
+      Token::new(Space, " ", 3, 4),
+      Token::new(LineComment, " comment", 4, 14),
+      Token::new(Space, "\n", 14, 15),
+      Token::new(Word, "2px", 15, 18),
+    ],
+  );
+}
+
+#[test]
+fn tokenizes_should_process_unclosed_line_comments_in_less() {
+  run_dialect(
+    Dialect::Less,
+    "red //",
+    vec![
+      Token::new(Word, "red", 0, 3),
+      Token::new(Space, " ", 3, 4),
+      Token::new(LineComment, "", 4, 6),
+    ],
+  );
+}
+
+#[test]
+fn tokenizes_should_not_treat_urls_as_line_comments() {
+  run_dialect(
+    Dialect::Scss,
+    "http://a",
+    vec![
+      Token::new(Word, "http", 0, 4),
+      Token::new(Div, ":", 4, 5),
+      Token::new(Div, "/", 5, 6),
+      Token::new(Div, "/", 6, 7),
+      Token::new(Word, "a", 7, 8),
+    ],
+  );
+}
+
+#[test]
+fn tokenizes_should_process_scss_interpolation() {
+  run_dialect(
+    Dialect::Scss,
+    "#{$a + 1}px calc(#{$b})",
+    vec![
+      Token::new(Word, "#{$a + 1}px", 0, 11),
+      Token::new(Space, " ", 11, 12),
+      Token::new(Word, "calc", 12, 16),
+      Token::new(OpenParentheses, "(", 16, 17),
+      Token::new(Word, "#{$b}", 17, 22),
+      Token::new(CloseParentheses, ")", 22, 23),
+    ],
+  );
+}
+
+#[test]
+fn tokenizes_should_process_nested_scss_interpolation() {
+  run_dialect(
+    Dialect::Scss,
+    "a-#{map-get($m, \"}\")}-b",
+    vec![Token::new(Word, "a-#{map-get($m, \"}\")}-b", 0, 23)],
+  );
+}
+
+#[test]
+fn tokenizes_should_process_unclosed_scss_interpolation() {
+  run_dialect(
+    Dialect::Scss,
+    "#{$a (",
+    vec![Token::new(Word, "#{$a (", 0, 6)],
+  );
+}
+
+#[test]
+fn tokenizes_should_process_less_interpolation() {
+  run_dialect(
+    Dialect::Less,
+    "~\"@{var}\" @{a b}",
+    vec![
+      Token::new(Word, "~", 0, 1),
+      Token::new(String, "\"@{var}\"", 1, 9),
+      Token::new(Space, " ", 9, 10),
+      Token::new(Word, "@{a b}", 10, 16),
+    ],
+  );
+}
+
+#[test]
+fn tokenizes_should_process_variables_as_words() {
+  run_dialect(
+    Dialect::Scss,
+    "$a-b,$c",
+    vec![
+      Token::new(Word, "$a-b", 0, 4),
+      Token::new(Div, ",", 4, 5),
+      Token::new(Word, "$c", 5, 7),
+    ],
+  );
+  run_dialect(
+    Dialect::Less,
+    "@a @@b",
+    vec![
+      Token::new(Word, "@a", 0, 2),
+      Token::new(Space, " ", 2, 3),
+      Token::new(Word, "@@b", 3, 6),
+    ],
+  );
+}
+
+#[test]
+fn tokenizes_should_not_process_interpolation_in_css() {
+  run(
+    "#{a b}",
+    vec![
+      Token::new(Word, "#{a", 0, 3),
+      Token::new(Space, " ", 3, 4),
+      Token::new(Word, "b}", 4, 6),
+    ],
+  );
+}
+
+fn with_placeholder(placeholder: PlaceholderPattern) -> TokenizerOptions {
+  TokenizerOptions {
+    placeholder: Some(placeholder),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn tokenizes_should_process_template_expression_placeholders() {
+  run_with_options(
+    with_placeholder(PlaceholderPattern::TemplateExpression),
+    "${p => p.size}px solid ${({ theme }) => theme.color}",
+    vec![
+      Token::new(Placeholder, "${p => p.size}", 0, 14),
+      Token::new(Word, "px", 14, 16),
+      Token::new(Space, " ", 16, 17),
+      Token::new(Word, "solid", 17, 22),
+      Token::new(Space, " ", 22, 23),
+      Token::new(Placeholder, "${({ theme }) => theme.color}", 23, 52),
+    ],
+  );
+}
+
+#[test]
+fn tokenizes_should_process_placeholders_inside_words_and_functions() {
+  run_with_options(
+    with_placeholder(PlaceholderPattern::TemplateExpression),
+    "calc(1${u}*2)",
+    vec![
+      Token::new(Word, "calc", 0, 4),
+      Token::new(OpenParentheses, "(", 4, 5),
+      Token::new(Word, "1", 5, 6),
+      Token::new(Placeholder, "${u}", 6, 10),
+      Token::new(Word, "*", 10, 11),
+      Token::new(Word, "2", 11, 12),
+      Token::new(CloseParentheses, ")", 12, 13),
+    ],
+  );
+}
+
+#[test]
+fn tokenizes_should_process_delimited_placeholders() {
+  run_with_options(
+    with_placeholder(PlaceholderPattern::Delimited {
+      prefix: "__PLACEHOLDER_",
+      suffix: "__",
+    }),
+    "__PLACEHOLDER_3__ a__PLACEHOLDER_12__, __PLACEHOLDER_",
+    vec![
+      Token::new(Placeholder, "__PLACEHOLDER_3__", 0, 17),
+      Token::new(Space, " ", 17, 18),
+      Token::new(Word, "a", 18, 19),
+      Token::new(Placeholder, "__PLACEHOLDER_12__", 19, 37),
+      Token::new(Div, ",", 37, 38),
+      Token::new(Space, " ", 38, 39),
+      Token::new(Word, "__PLACEHOLDER_", 39, 53),
+    ],
+  );
+}
+
+#[test]
+fn tokenizes_should_keep_placeholders_inside_strings() {
+  run_with_options(
+    with_placeholder(PlaceholderPattern::TemplateExpression),
+    "\"${a}\"",
+    vec![Token::new(String, "\"${a}\"", 0, 6)],
+  );
 }
@@ -0,0 +1,158 @@
+use postcss_value_parser::calc::Expr;
+use postcss_value_parser::parser::parse;
+use postcss_value_parser::quantity::Quantity;
+use postcss_value_parser::transform::{Matrix, TransformFunction, TransformList};
+
+fn list(value: &str) -> Option<TransformList> {
+  TransformList::parse(&parse(value))
+}
+
+fn shortest(value: &str) -> String {
+  list(value).unwrap().to_string()
+}
+
+fn assert_matrix_close(actual: Matrix, expected: [f64; 16]) {
+  for (actual, expected) in actual.0.iter().zip(expected) {
+    assert!(
+      (actual - expected).abs() < 1e-9,
+      "{:?} is not {:?}",
+      actual,
+      expected
+    );
+  }
+}
+
+#[test]
+fn parses_transform_functions() {
+  let px = |value| Expr::Value(Quantity::new(value, "px"));
+  let zero = Expr::Value(Quantity::number(0.0));
+  assert_eq!(
+    list("translateX(10px) scaleY(50%) ROTATEZ(.25turn) perspective(none)").unwrap(),
+    TransformList(vec![
+      TransformFunction::Translate(px(10.0), zero.clone()),
+      TransformFunction::Scale(1.0, 0.5),
+      TransformFunction::Rotate(Expr::Value(Quantity::new(0.25, "turn"))),
+      TransformFunction::Perspective(None),
+    ])
+  );
+  assert_eq!(
+    list("translateZ(calc(1px + 2px))").unwrap(),
+    TransformList(vec![TransformFunction::Translate3d(
+      zero.clone(),
+      zero,
+      px(3.0)
+    )])
+  );
+  assert_eq!(list("none"), Some(TransformList::default()));
+  for invalid in [
+    "",
+    "translate(10px 20px)",
+    "translate(10deg)",
+    "translateZ(10%)",
+    "rotate(10px)",
+    "rotate(1)",
+    "scale(1px)",
+    "matrix(1, 0, 0, 1, 0)",
+    "perspective(-1px)",
+    "translate(1px) red",
+    "foo(1px)",
+  ] {
+    assert_eq!(list(invalid), None, "{}", invalid);
+  }
+}
+
+#[test]
+fn serializes_shortest_form() {
+  let tests = [
+    ("translate(10px, 0)", "translate(10px)"),
+    ("translate(0px, 10px)", "translateY(10px)"),
+    (
+      "translateX(10%) translateY(5em)",
+      "translate(10%) translateY(5em)",
+    ),
+    ("translate3d(0, 0, 5px)", "translateZ(5px)"),
+    ("translate3d(0, 0, 0)", "translateZ(0)"),
+    ("scale(2, 2)", "scale(2)"),
+    ("scale(2, 1)", "scaleX(2)"),
+    ("scale(100%, 150%)", "scaleY(1.5)"),
+    ("scale3d(1, 1, 2)", "scaleZ(2)"),
+    ("rotate(0deg) scale(2)", "scale(2)"),
+    ("rotateZ(45deg)", "rotate(45deg)"),
+    ("rotate3d(0, 0, 2, 45deg)", "rotate(45deg)"),
+    ("rotate3d(2, 0, 0, 45deg)", "rotateX(45deg)"),
+    ("rotate3d(1, 1, 0, 45deg)", "rotate3d(1, 1, 0, 45deg)"),
+    ("skew(10deg, 0deg)", "skew(10deg)"),
+    ("skewX(0) skewY(10deg)", "skewY(10deg)"),
+    ("matrix(1.0, 0, 0, 1, 10, 0)", "matrix(1, 0, 0, 1, 10, 0)"),
+    (
+      "matrix3d(1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 10, 20, 0, 1)",
+      "matrix(1, 0, 0, 1, 10, 20)",
+    ),
+    (
+      "translate(calc(50% + 10px), 0)",
+      "translate(calc(50% + 10px))",
+    ),
+    ("perspective(0)", "perspective(0)"),
+    // a transform is kept when every function is an identity
+    ("rotate(0deg)", "rotate(0)"),
+    ("translate(0) scale(1)", "translate(0)"),
+    ("scale(1) rotate(0deg)", "scale(1)"),
+    ("translateZ(0)", "translateZ(0)"),
+    ("none", "none"),
+  ];
+  for (value, expected) in tests {
+    assert_eq!(shortest(value), expected, "{}", value);
+  }
+}
+
+#[test]
+fn composes_matrices() {
+  let matrix = list("translate(10px, 20px) scale(2)")
+    .unwrap()
+    .to_matrix()
+    .unwrap();
+  assert_eq!(matrix.to_string(), "matrix(2, 0, 0, 2, 10, 20)");
+  // the translation is scaled when it comes after
+  let matrix = list("scale(2) translate(10px, 20px)")
+    .unwrap()
+    .to_matrix()
+    .unwrap();
+  assert_eq!(matrix.to_string(), "matrix(2, 0, 0, 2, 20, 40)");
+
+  let matrix = list("rotate(90deg)").unwrap().to_matrix().unwrap();
+  assert_eq!(matrix.to_string(), "matrix(0, 1, -1, 0, 0, 0)");
+  let matrix = list("rotate(.25turn) translateX(1in)")
+    .unwrap()
+    .to_matrix()
+    .unwrap();
+  assert_eq!(matrix.to_string(), "matrix(0, 1, -1, 0, 0, 96)");
+  let matrix = list("skewX(45deg)").unwrap().to_matrix().unwrap();
+  assert_eq!(matrix.to_string(), "matrix(1, 0, 1, 1, 0, 0)");
+
+  let matrix = list("perspective(100px) translateZ(10px)")
+    .unwrap()
+    .to_matrix()
+    .unwrap();
+  assert_matrix_close(
+    matrix,
+    [
+      1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, -0.01, 0.0, 0.0, 10.0, 0.9,
+    ],
+  );
+  assert_eq!(
+    matrix.to_string(),
+    "matrix3d(1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1, -.01, 0, 0, 10, .9)"
+  );
+  let matrix = list("rotateX(90deg)").unwrap().to_matrix().unwrap();
+  assert_matrix_close(
+    matrix,
+    [
+      1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+    ],
+  );
+  assert_eq!(list("none").unwrap().to_matrix(), Some(Matrix::IDENTITY));
+
+  // relative lengths are only known at layout time
+  assert_eq!(list("translate(50%)").unwrap().to_matrix(), None);
+  assert_eq!(list("translate(1em)").unwrap().to_matrix(), None);
+}
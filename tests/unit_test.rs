@@ -1,4 +1,4 @@
-use postcss_value_parser::unit::{unit, Dimension};
+use postcss_value_parser::unit::{convert, unit, Dimension, NumberType, Sign, UnitKind};
 
 #[test]
 fn test_unit() {
@@ -86,3 +86,100 @@ fn test_unit() {
     assert_eq!(unit(fixture), expected);
   }
 }
+
+#[test]
+fn test_dimension_value() {
+  let tests = vec![
+    ("10px", 10.0, NumberType::Integer, None),
+    ("+.5px", 0.5, NumberType::Number, Some(Sign::Plus)),
+    ("-2.", -2.0, NumberType::Integer, Some(Sign::Minus)),
+    ("1e+3em", 1000.0, NumberType::Number, None),
+    ("1.5E-1", 0.15, NumberType::Number, None),
+    ("-.0", 0.0, NumberType::Number, Some(Sign::Minus)),
+    ("007", 7.0, NumberType::Integer, None),
+  ];
+
+  for (fixture, value, number_type, sign) in tests {
+    let dimension = unit(fixture).unwrap();
+    assert_eq!(dimension.value(), value, "{}", fixture);
+    assert_eq!(dimension.number_type(), number_type, "{}", fixture);
+    assert_eq!(dimension.sign(), sign, "{}", fixture);
+  }
+
+  assert!(unit("-0").unwrap().value().is_sign_negative());
+  assert!(Dimension::new("1x", "").value().is_nan());
+}
+
+#[test]
+fn test_unit_kind() {
+  let tests = vec![
+    ("3Q", UnitKind::AbsoluteLength),
+    ("1PX", UnitKind::AbsoluteLength),
+    ("2in", UnitKind::AbsoluteLength),
+    ("1.5rem", UnitKind::FontRelativeLength),
+    ("1Ch", UnitKind::FontRelativeLength),
+    ("100svh", UnitKind::ViewportLength),
+    ("100dvmax", UnitKind::ViewportLength),
+    ("5cqi", UnitKind::ContainerLength),
+    ("90deg", UnitKind::Angle),
+    ("1turn", UnitKind::Angle),
+    ("200ms", UnitKind::Time),
+    ("1kHz", UnitKind::Frequency),
+    ("2x", UnitKind::Resolution),
+    ("96dpi", UnitKind::Resolution),
+    ("1fr", UnitKind::Flex),
+    ("50%", UnitKind::Percentage),
+    ("1", UnitKind::Number),
+    ("1foo", UnitKind::Unknown),
+    ("1pxpxpx", UnitKind::Unknown),
+  ];
+
+  for (fixture, expected) in tests {
+    assert_eq!(unit(fixture).unwrap().kind(), expected, "{}", fixture);
+  }
+
+  assert!(UnitKind::of("vmin").is_length());
+  assert!(!UnitKind::of("%").is_length());
+}
+
+#[test]
+fn test_convert() {
+  let tests = vec![
+    ("1in", "px", Some(96.0)),
+    ("2.54cm", "in", Some(1.0)),
+    ("10mm", "cm", Some(1.0)),
+    ("40Q", "cm", Some(1.0)),
+    ("72pt", "in", Some(1.0)),
+    ("1pc", "pt", Some(12.0)),
+    ("12px", "pt", Some(9.0)),
+    ("180deg", "turn", Some(0.5)),
+    ("100grad", "deg", Some(90.0)),
+    ("1turn", "rad", Some(std::f64::consts::PI * 2.0)),
+    ("250ms", "s", Some(0.25)),
+    ("2kHz", "Hz", Some(2000.0)),
+    ("96dpi", "dppx", Some(1.0)),
+    ("2x", "dppx", Some(2.0)),
+    ("1dppx", "dpcm", Some(96.0 / 2.54)),
+    ("50%", "%", Some(50.0)),
+    ("2em", "EM", Some(2.0)),
+    ("1px", "s", None),
+    ("1em", "px", None),
+    ("1", "px", None),
+    ("1px", "foo", None),
+  ];
+
+  for (fixture, target, expected) in tests {
+    let converted = convert(&unit(fixture).unwrap(), target);
+    match (converted, expected) {
+      (Some(converted), Some(expected)) => {
+        assert!(
+          (converted - expected).abs() < 1e-9,
+          "{} -> {}",
+          fixture,
+          target
+        )
+      }
+      _ => assert_eq!(converted, expected, "{} -> {}", fixture, target),
+    }
+  }
+}